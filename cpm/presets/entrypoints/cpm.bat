@@ -0,0 +1,20 @@
+@echo off
+rem CPM entrypoint, generated by 'cpm init'. Regenerate with 'cpm init --entrypoint-only'.
+setlocal
+{{#if relative}}
+set "CPM_EXE=%~dp0{{exe_path_windows}}"
+{{else}}
+set "CPM_EXE={{exe_path_windows}}"
+{{/if}}
+
+rem Fall back to the executable on PATH if the project or the executable was moved
+if not exist "%CPM_EXE%" (
+    for %%I in ({{exe_name}}) do set "CPM_EXE=%%~$PATH:I"
+)
+if not defined CPM_EXE (
+    echo cpm: executable not found: {{exe_path_windows}} 1>&2
+    exit /b 1
+)
+
+rem On a single line, cmd.exe does not read this file again after the call ('cpm deinit' deletes it)
+"%CPM_EXE%" --no-init %* & exit /b
@@ -0,0 +1,20 @@
+# CPM entrypoint, generated by 'cpm init'. Regenerate with 'cpm init --entrypoint-only'.
+{{#if relative}}
+$CpmExe = Join-Path $PSScriptRoot '{{exe_path_powershell}}'
+{{else}}
+$CpmExe = '{{exe_path_powershell}}'
+{{/if}}
+
+# Fall back to the executable on PATH if the project or the executable was moved
+if (-not (Test-Path -LiteralPath $CpmExe -PathType Leaf)) {
+    $Command = Get-Command '{{exe_name}}' -CommandType Application -ErrorAction SilentlyContinue |
+        Select-Object -First 1
+    if (-not $Command) {
+        Write-Error "cpm: executable not found: {{exe_path_windows}}"
+        exit 1
+    }
+    $CpmExe = $Command.Source
+}
+
+& $CpmExe --no-init @args
+exit $LASTEXITCODE
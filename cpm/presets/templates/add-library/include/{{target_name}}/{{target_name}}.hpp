@@ -0,0 +1,8 @@
+#pragma once
+
+#include <string>
+
+namespace {{target_identifier}}
+{
+    std::string hello();
+}
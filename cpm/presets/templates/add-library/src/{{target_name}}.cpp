@@ -0,0 +1,9 @@
+#include "{{target_name}}/{{target_name}}.hpp"
+
+namespace {{target_identifier}}
+{
+    std::string hello()
+    {
+        return "Hello from {{target_name}}!";
+    }
+}
@@ -0,0 +1,8 @@
+#include <iostream>
+
+// CTest fails the test when main returns a non-zero exit code
+int main()
+{
+    std::cout << "Running {{target_name}}" << std::endl;
+    return 0;
+}
@@ -0,0 +1,7 @@
+#include <iostream>
+
+int main()
+{
+    std::cout << "Hello from {{project_name}}!" << std::endl;
+    return 0;
+}
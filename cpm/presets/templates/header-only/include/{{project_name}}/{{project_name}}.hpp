@@ -0,0 +1,11 @@
+#pragma once
+
+#include <string>
+
+namespace {{project_identifier}}
+{
+    inline std::string hello()
+    {
+        return "Hello from {{project_name}}!";
+    }
+}
@@ -0,0 +1,9 @@
+#include "{{project_name}}/{{project_name}}.hpp"
+
+namespace {{project_identifier}}
+{
+    std::string hello()
+    {
+        return "Hello from {{project_name}}!";
+    }
+}
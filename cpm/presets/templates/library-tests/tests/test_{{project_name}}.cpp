@@ -0,0 +1,13 @@
+#include "{{project_name}}/{{project_name}}.hpp"
+
+#include <iostream>
+
+int main()
+{
+    if ({{project_identifier}}::hello() != "Hello from {{project_name}}!")
+    {
+        std::cerr << "hello() returned an unexpected greeting" << std::endl;
+        return 1;
+    }
+    return 0;
+}
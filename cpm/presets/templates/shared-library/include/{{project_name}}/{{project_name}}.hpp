@@ -0,0 +1,8 @@
+#pragma once
+
+#include <string>
+
+namespace {{project_identifier}}
+{
+    std::string hello();
+}
@@ -0,0 +1,408 @@
+use spdlog::prelude::*;
+use std::fs;
+use std::path::{ Component, Path, PathBuf };
+
+use crate::commands::{ AddArgs, AddCommands, AddPackageArgs, AddTargetArgs };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::cmake_lists;
+use crate::internal::cmd;
+use crate::internal::install::{ self, Package };
+use crate::internal::package_usage::{ self, Usage };
+use crate::internal::settings::Settings;
+use crate::internal::templates;
+use crate::internal::variant;
+
+const DEFAULT_TRIPLET: &str = "x64-windows";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TargetKind {
+    Executable,
+    Library,
+    Test,
+}
+
+impl TargetKind {
+    fn name(&self) -> &'static str {
+        match self {
+            TargetKind::Executable => "executable",
+            TargetKind::Library => "library",
+            TargetKind::Test => "test",
+        }
+    }
+
+    // Embedded template, can be overridden like the project templates
+    fn template(&self) -> String {
+        format!("add-{}", self.name())
+    }
+
+    fn default_dir(&self, name: &str) -> String {
+        match self {
+            TargetKind::Test => format!("tests/{}", name),
+            _ => name.to_string(),
+        }
+    }
+}
+
+pub fn run(args: AddArgs) {
+    debug!("Running the Add command with arguments: {:#?}", args);
+
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    match args.command {
+        AddCommands::Executable(target_args) => {
+            add_target(&settings, TargetKind::Executable, target_args)
+        }
+        AddCommands::Library(target_args) => {
+            add_target(&settings, TargetKind::Library, target_args)
+        }
+        AddCommands::Test(target_args) => {
+            add_target(&settings, TargetKind::Test, target_args)
+        }
+        AddCommands::Package(package_args) => add_package(&settings, package_args),
+    }
+}
+
+fn add_target(settings: &Settings, kind: TargetKind, args: AddTargetArgs) {
+    let valid_name =
+        !args.name.is_empty() &&
+        args.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        RuntimeErrors::InvalidTargetName(args.name.clone()).exit();
+    }
+
+    let project_root = PathBuf::from(&settings.working_dir);
+    if !cmake_lists::file_path(&project_root).exists() {
+        RuntimeErrors::CMakeListsNotFound(project_root.display().to_string()).exit();
+    }
+
+    let dir = args.dir.clone().unwrap_or(kind.default_dir(&args.name)).replace('\\', "/");
+    let inside_project = Path::new(&dir)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !inside_project || dir.trim_matches(|c| c == '/' || c == '.').is_empty() {
+        RuntimeErrors::AddTargetFailed(
+            format!("'{}' is not a subdirectory of the project", dir)
+        ).exit();
+    }
+    let destination = project_root.join(dir.trim_matches('/'));
+
+    // The closest CMakeLists.txt above the new directory adds it, at the latest the root one
+    let parent_dir = destination
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.starts_with(&project_root) && cmake_lists::file_path(dir).exists())
+        .unwrap_or(&project_root)
+        .to_path_buf();
+    let parent_file = cmake_lists::file_path(&parent_dir);
+    let subdirectory = destination
+        .strip_prefix(&parent_dir)
+        .unwrap_or(&destination)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    // Check the parent before creating any file, so a refused edit leaves the project untouched
+    let contents = match fs::read_to_string(&parent_file) {
+        Ok(contents) => contents,
+        Err(e) => {
+            RuntimeErrors::AddTargetFailed(format!("{}: {}", parent_file.display(), e)).exit();
+            return;
+        }
+    };
+    let edited = cmake_lists
+        ::parse(&contents)
+        .and_then(|commands| cmake_lists::insert_subdirectory(&contents, &commands, &subdirectory));
+    let edited = match edited {
+        Ok(edited) => edited,
+        Err(reason) => {
+            RuntimeErrors::UnsafeCMakeListsEdit(parent_file.display().to_string(), reason).exit();
+            return;
+        }
+    };
+
+    let template_name = kind.template();
+    let Some(template) = templates::find_target(settings, &template_name) else {
+        let available = "add-executable, add-library, add-test".to_string();
+        RuntimeErrors::TemplateNotFound(template_name, available).exit();
+        return;
+    };
+
+    let mut defines = match templates::parse_defines(&args.defines) {
+        Ok(defines) => defines,
+        Err(define) => {
+            RuntimeErrors::InvalidTemplateVariable(define).exit();
+            return;
+        }
+    };
+    if !args.link.is_empty() {
+        defines.insert("link".to_string(), args.link.join(","));
+    }
+
+    info!(
+        "Adding {} '{}' from the {} '{}' template in: {}",
+        kind.name(),
+        args.name,
+        template.source.name(),
+        template.name,
+        destination.display()
+    );
+
+    let variables = templates::target_variables(&template, &args.name, &defines);
+    match templates::render(&template, &variables, &destination, args.force) {
+        Ok((written, skipped)) => {
+            for path in &written {
+                info!("Created: {}", relative(&project_root, path));
+            }
+            for path in &skipped {
+                warn!(
+                    "Skipped existing file (use --force to overwrite): {}",
+                    relative(&project_root, path)
+                );
+            }
+        }
+        Err(e) => RuntimeErrors::AddTargetFailed(e.to_string()).exit(),
+    }
+
+    match edited {
+        Some(edited) => {
+            if let Err(e) = fs::write(&parent_file, edited) {
+                RuntimeErrors::AddTargetFailed(format!("{}: {}", parent_file.display(), e)).exit();
+            }
+            info!(
+                "Added 'add_subdirectory({})' to: {}",
+                subdirectory,
+                relative(&project_root, &parent_file)
+            );
+        }
+        None => {
+            info!(
+                "'{}' is already added by: {}",
+                subdirectory,
+                relative(&project_root, &parent_file)
+            );
+        }
+    }
+
+    if kind == TargetKind::Test && !testing_enabled(&project_root) {
+        warn!("No enable_testing() or include(CTest) in the project, CTest will not find the test.");
+    }
+
+    info!("Run 'cpm build -g' to generate the project with the new target.");
+}
+
+fn add_package(settings: &Settings, args: AddPackageArgs) {
+    let config_path = Path::new(&settings.install_json_path);
+    let contents = match fs::read_to_string(config_path) {
+        Ok(contents) => contents,
+        Err(_) => {
+            RuntimeErrors::JSONFileNotFound(Some(settings.install_json_path.clone())).exit();
+            return;
+        }
+    };
+
+    // Same triplet as the packages already listed, unless given
+    let triplet = args.triplet.clone().unwrap_or_else(|| {
+        install
+            ::retrieve_install(config_path)
+            .ok()
+            .and_then(|config| config.config.windows)
+            .and_then(|windows| windows.packages.first().map(|package| package.triplet.clone()))
+            .unwrap_or(DEFAULT_TRIPLET.to_string())
+    });
+
+    let package = Package { library: args.name.clone(), triplet: triplet.clone() };
+    match install::add_package(&contents, &package) {
+        Ok(Some(edited)) => {
+            if let Err(e) = fs::write(config_path, edited) {
+                let path = settings.install_json_path.clone();
+                RuntimeErrors::InstallConfigEditFailed(path, e.to_string()).exit();
+            }
+            info!(
+                "Added package '{}' ({}) to: {}",
+                package.library,
+                triplet,
+                config_path.display()
+            );
+        }
+        Ok(None) => {
+            info!("Package '{}' is already listed in: {}", package.library, config_path.display());
+        }
+        Err(reason) => {
+            let path = settings.install_json_path.clone();
+            RuntimeErrors::InstallConfigEditFailed(path, reason).exit();
+        }
+    }
+
+    if args.install {
+        if settings.toolchain_path.is_empty() {
+            RuntimeErrors::ToolchainNotFound("vcpkg".to_string()).exit();
+        }
+        let vcpkg_exe = format!("{}\\vcpkg.exe", settings.toolchain_path);
+        let status = cmd::execute_interactive(
+            vec![
+                vcpkg_exe,
+                "install".to_string(),
+                package.library.clone(),
+                format!("--triplet={}", triplet)
+            ],
+            vec![]
+        );
+        if status != 0 {
+            RuntimeErrors::PackageInstallFailed(Some(package.library.clone())).exit();
+        }
+        info!("Installed package: {}", package.library);
+    } else {
+        info!("Run 'cpm setup' or pass --install to install the package.");
+    }
+
+    let usage = if settings.toolchain_path.is_empty() {
+        None
+    } else {
+        package_usage::load(&settings.toolchain_path, &triplet, &package.library)
+    };
+
+    if args.insert {
+        let target = args.target.clone().unwrap_or_default();
+        match usage {
+            Some(usage) if usage.is_complete() => insert_package_usage(settings, &usage, &target),
+            _ => RuntimeErrors::PackageUsageNotFound(package.library.clone()).exit(),
+        }
+        return;
+    }
+
+    let target = args.target.clone().unwrap_or("<target>".to_string());
+    match usage {
+        Some(usage) if usage.is_complete() => {
+            info!("Use the package from CMake with:");
+            info!("    {}", usage.find_package.clone().unwrap_or_default());
+            info!("    {}", usage.link_call(&target));
+        }
+        Some(usage) => {
+            info!("Usage of '{}':", package.library);
+            for line in usage.text.lines() {
+                info!("    {}", line);
+            }
+        }
+        None => {
+            warn!(
+                "The usage of '{}' is known once it is installed. Usually:\n    find_package({} CONFIG REQUIRED)",
+                package.library,
+                package.library
+            );
+        }
+    }
+}
+
+// Adds find_package before and target_link_libraries after the definition of the target.
+fn insert_package_usage(settings: &Settings, usage: &Usage, target: &str) {
+    let project_root = PathBuf::from(&settings.working_dir);
+    let package = usage.package().unwrap_or_default();
+
+    let mut listfiles: Vec<(PathBuf, String, Vec<cmake_lists::Command>)> = Vec::new();
+    for path in project_listfiles(settings) {
+        match cmake_lists::load(&path) {
+            Ok((contents, commands)) => listfiles.push((path, contents, commands)),
+            Err(e) => debug!("Skipping {}: {}", path.display(), e),
+        }
+    }
+
+    let already_found = listfiles.iter().any(|(_, _, commands)| {
+        commands.iter().any(|command| {
+            command.name == "find_package" &&
+                command
+                    .argument_list()
+                    .first()
+                    .map(|name| name.eq_ignore_ascii_case(&package))
+                    .unwrap_or(false)
+        })
+    });
+
+    let definition = listfiles.iter().find_map(|(path, contents, commands)| {
+        commands
+            .iter()
+            .find(|command| {
+                matches!(command.name.as_str(), "add_executable" | "add_library") &&
+                    command.argument_list().first().map(|name| name.as_str()) == Some(target)
+            })
+            .map(|command| (path, contents, commands, command))
+    });
+    let Some((path, contents, commands, definition)) = definition else {
+        RuntimeErrors::TargetDefinitionNotFound(target.to_string()).exit();
+        return;
+    };
+
+    let already_linked = commands.iter().any(|command| {
+        let arguments = command.argument_list();
+        command.name == "target_link_libraries" &&
+            arguments.first().map(|name| name.as_str()) == Some(target) &&
+            usage.link_targets.iter().all(|link| arguments.contains(link))
+    });
+
+    let relative = path.strip_prefix(&project_root).unwrap_or(path).display().to_string();
+
+    let link_call = usage.link_call(target);
+    if already_linked {
+        info!("'{}' already links the package in: {}", target, relative);
+    } else {
+        info!("Added '{}' to: {}", link_call, relative);
+    }
+    let find_package = usage.find_package.clone().unwrap_or_default();
+    if already_found {
+        info!("'{}' is already found by the project", package);
+    } else {
+        info!("Added '{}' to: {}", find_package, relative);
+    }
+    let edited = cmake_lists::insert_around(
+        contents,
+        definition,
+        (!already_found).then_some(find_package.as_str()),
+        (!already_linked).then_some(link_call.as_str())
+    );
+
+    if edited != *contents {
+        if let Err(e) = fs::write(path, edited) {
+            RuntimeErrors::AddTargetFailed(format!("{}: {}", path.display(), e)).exit();
+        }
+    }
+}
+
+// CMakeLists.txt files of the project, without the build, install and dist trees.
+pub fn project_listfiles(settings: &Settings) -> Vec<PathBuf> {
+    let excluded = vec![
+        variant::base_build_dir(&settings.build_dir, &settings.build_variant),
+        PathBuf::from(&settings.install_dir),
+        PathBuf::from(&settings.dist_dir)
+    ];
+    cmake_lists::project_files(Path::new(&settings.working_dir), &excluded)
+}
+
+fn testing_enabled(project_root: &Path) -> bool {
+    let Ok((_, commands)) = cmake_lists::load(&cmake_lists::file_path(project_root)) else {
+        return false;
+    };
+    commands.iter().any(|command| {
+        let module = command.argument_list().first().cloned().unwrap_or_default();
+        command.name == "enable_testing" || (command.name == "include" && module == "CTest")
+    })
+}
+
+fn relative(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base).unwrap_or(path).display().to_string()
+}
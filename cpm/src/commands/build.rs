@@ -1,11 +1,23 @@
 use spdlog::prelude::*;
-use std::path::Path;
-use walkdir::WalkDir;
+use std::path::{ Path, PathBuf };
+use std::time::Instant;
 
-use crate::commands::BuildArgs;
+use crate::commands::{ BuildArgs, CleanArgs };
+use crate::commands::clean;
+use crate::commands::stats;
+use crate::commands::package::dist_dir;
 use crate::errors::errors::RuntimeErrors;
 use crate::internal::settings::Settings;
-use crate::internal::cmd;
+use crate::internal::cmd::{ self, CommandOutput };
+use crate::internal::build_report::BuildReport;
+use crate::internal::file_api;
+use crate::internal::fingerprint::{ self, Fingerprint };
+use crate::internal::configure_command::{ ConfigureCommand, Definition };
+use crate::internal::compile_commands::{ self, ExportMode };
+use crate::internal::install::retrieve_install;
+use crate::internal::launcher::{ self, Launcher };
+use crate::internal::variant::{ self, Variant };
+use crate::internal::stage::{ self, LinkMode };
 
 pub fn run(args: BuildArgs) {
     debug!("Running the Initialization command with arguments: {:#?}", args);
@@ -32,24 +44,26 @@ pub fn run(args: BuildArgs) {
         RuntimeErrors::ProjectNotInitialized.exit();
     }
 
-    // 'b' is for 'Build' folder and 'i' is for 'Install' folder in the working directory.
-    // Both chars can be used to clean the respective folders.
+    // 'b' is for 'Build' folder, 'i' is for 'Install' folder and 'p' is for 'Dist' folder in the working directory.
+    // The chars can be combined to clean the respective folders.
     // Example: 'bi' will clean both folders.
     // Need to parse the string and clean the respective folders.
     if let Some(maybe_what_to_clean) = &args.clean_project {
-        match maybe_what_to_clean {
-            Some(what_to_clean) if !what_to_clean.trim().is_empty() => {
-                clean_cmake_project(&settings, what_to_clean);
-            }
+        let what_to_clean = match maybe_what_to_clean {
+            Some(what_to_clean) if !what_to_clean.trim().is_empty() => what_to_clean.clone(),
             _ => {
                 warn!(
                     "No arguments provided for cleaning. Cleaning both 'Build' and 'Install' folders."
                 );
-                clean_cmake_project(&settings, "bi");
+                "bi".to_string()
             }
-        }
+        };
+        clean::clean_cmake_project(&settings, &CleanArgs::new(&what_to_clean));
     }
 
+    // Timings and diagnostics of the configure, build and install steps
+    let mut report = BuildReport::new(if args.debug_build_type { "Debug" } else { "Release" });
+
     if let Some(maybe_generate_args) = &args.generate_project {
         check_build_type(&args);
 
@@ -61,28 +75,55 @@ pub fn run(args: BuildArgs) {
             "Release"
         };
 
-        match maybe_generate_args {
+        let started = Instant::now();
+        let output = match maybe_generate_args {
             Some(generate_args) if !generate_args.trim().is_empty() => {
                 info!(
                     "Generating CMake project for system type '{}' with build type '{}'",
                     generate_args,
                     build_type
                 );
-                generate_cmake_project(&mut settings, generate_args, build_type);
+                generate_cmake_project(&mut settings, generate_args, build_type, &args)
             }
             _ => {
                 warn!(
                     "No system type provided or empty. Will attempt to use the last cmake configuration command."
                 );
-                let last_cmd = &settings.last_cmake_configuration_command;
+                // The variant is part of the cached command and build directory
+                if args.sanitize.is_some() || args.coverage {
+                    RuntimeErrors::InvalidVariant(
+                        "a system type is required to generate a build variant".to_string()
+                    ).exit();
+                }
+
+                let last_cmd = settings.last_cmake_configuration_command.clone();
                 if !last_cmd.is_empty() {
-                    cmd::execute_and_display_output(last_cmd.clone());
+                    // The cached command already contains the launcher, only change it when asked to
+                    let last_cmd = match &args.launcher {
+                        Some(launcher) => {
+                            let system_type = settings.cmake_system_type.clone();
+                            apply_compiler_launcher(last_cmd, &system_type, launcher)
+                        }
+                        None => last_cmd,
+                    };
+                    let last_cmd = apply_definition_overrides(last_cmd, &args);
+                    // Save the merged command so the overrides stick for the next replay
+                    settings.last_cmake_configuration_command = last_cmd.clone();
+                    let _ = settings.save_default();
+                    configure_cmake_project(&mut settings, last_cmd, args.reconfigure)
                 } else {
                     error!("No previous CMake configuration command available.");
+                    None
                 }
             }
+        };
+        report.add_step("configure", started, output.as_ref());
+        if report.failed_step().is_some() {
+            finish_report(&settings, &report, &args);
         }
 
+        export_compile_commands(&settings, &args);
+
         // if generate_args.trim().is_empty() {
         //     warn!("No system type provided. Will attempt to use last cmake configuration command.");
 
@@ -132,11 +173,43 @@ pub fn run(args: BuildArgs) {
             "Release"
         };
 
-        build_cmake_project(&settings, build_type);
+        let started = Instant::now();
+        let output = build_cmake_project(&settings, build_type);
+        report.add_step("build", started, Some(&output));
+        if report.failed_step().is_some() {
+            finish_report(&settings, &report, &args);
+        }
+
+        if args.profile && stats::print_build_profile(&mut settings, 10).is_err() {
+            warn!("No Ninja build log found. Profiling is only available with the Ninja generator.");
+        }
 
         info!("Project built successfully.");
     }
 
+    if let Some(maybe_stage_mode) = &args.stage_assets {
+        check_build_type(&args);
+
+        let build_type = if args.debug_build_type { "Debug" } else { "Release" };
+
+        let mode = match maybe_stage_mode {
+            Some(stage_mode) if !stage_mode.trim().is_empty() => {
+                match LinkMode::parse(stage_mode) {
+                    Some(mode) => mode,
+                    None => {
+                        RuntimeErrors::StageAssetsFailed(
+                            Some(format!("Invalid stage mode '{}'", stage_mode))
+                        ).exit();
+                        return;
+                    }
+                }
+            }
+            _ => LinkMode::Symlink,
+        };
+
+        stage_runtime_assets(&settings, build_type, mode);
+    }
+
     if args.install_project {
         check_build_type(&args);
 
@@ -149,10 +222,41 @@ pub fn run(args: BuildArgs) {
             "Release"
         };
 
-        install_cmake_project(&settings, build_type);
+        let started = Instant::now();
+        let output = install_cmake_project(&settings, build_type);
+        report.add_step("install", started, Some(&output));
+        if report.failed_step().is_some() {
+            finish_report(&settings, &report, &args);
+        }
 
         info!("Project installed successfully.");
     }
+
+    if !report.steps.is_empty() {
+        finish_report(&settings, &report, &args);
+    }
+}
+
+// Prints the build summary, writes it as JSON if requested and exits if one of the steps failed.
+fn finish_report(settings: &Settings, report: &BuildReport, args: &BuildArgs) {
+    report.print();
+
+    if let Some(maybe_report_path) = &args.report {
+        let report_path = match maybe_report_path {
+            Some(report_path) if !report_path.trim().is_empty() => {
+                Path::new(&settings.working_dir).join(report_path)
+            }
+            _ => Path::new(&settings.build_dir).join("cpm_build_report.json"),
+        };
+        match report.write_json(&report_path) {
+            Ok(_) => info!("Build report written to: {}", report_path.display()),
+            Err(e) => warn!("Failed to write the build report: {}", e),
+        }
+    }
+
+    if let Some(step) = report.failed_step() {
+        RuntimeErrors::BuildStepFailed(step.name.clone(), step.status.unwrap_or(1)).exit();
+    }
 }
 
 fn check_build_type(args: &BuildArgs) {
@@ -168,9 +272,37 @@ fn check_build_type(args: &BuildArgs) {
     }
 }
 
-fn generate_cmake_project(settings: &mut Settings, system_type: &str, build_type: &str) {
+// Commands that act on an already generated project default to the cached build type.
+pub fn resolve_build_type(
+    debug_build_type: bool,
+    release_build_type: bool,
+    settings: &Settings
+) -> String {
+    if debug_build_type && release_build_type {
+        error!("Both debug and release build types set. Use only one.");
+        RuntimeErrors::BuildTypeBothSet.exit();
+    }
+
+    if debug_build_type {
+        "Debug".to_string()
+    } else if release_build_type {
+        "Release".to_string()
+    } else if !settings.cmake_build_type.is_empty() {
+        settings.cmake_build_type.clone()
+    } else {
+        error!("Build type not set. Pass the appropriate flag (-r or -d).");
+        RuntimeErrors::BuildTypeNotSet.exit();
+        String::new()
+    }
+}
+
+fn generate_cmake_project(
+    settings: &mut Settings,
+    system_type: &str,
+    build_type: &str,
+    args: &BuildArgs
+) -> Option<CommandOutput> {
     let source_dir = settings.working_dir.clone();
-    let build_dir = settings.build_dir.clone();
     let toolchain_path = settings.vcpkg_path.clone();
 
     // If system_type is "nt/msvc", then the toolchain path must be set.
@@ -181,9 +313,30 @@ fn generate_cmake_project(settings: &mut Settings, system_type: &str, build_type
         RuntimeErrors::GenerateProjectNtMsvcNoToolchain.exit();
     }
 
+    let build_variant = match Variant::parse(args.sanitize.as_deref(), args.coverage) {
+        Ok(build_variant) => build_variant,
+        Err(reason) => {
+            RuntimeErrors::InvalidVariant(reason).exit();
+            Variant::default()
+        }
+    };
+    if let Err(reason) = build_variant.validate(system_type, build_type) {
+        RuntimeErrors::VariantNotSupported(reason).exit();
+    }
+    let build_dir = select_build_variant(settings, &build_variant);
+
     // Prepare the presets
     // Match system type string
-    let preset = generate_preset(&system_type, &source_dir, &build_dir, &toolchain_path);
+    let preset = generate_preset(
+        system_type,
+        &source_dir,
+        &build_dir,
+        &toolchain_path,
+        &build_variant
+    );
+    let launcher = args.launcher.clone().unwrap_or(settings.compiler_launcher.clone());
+    let preset = apply_compiler_launcher(preset, system_type, &launcher);
+    let preset = apply_definition_overrides(preset, args);
 
     // Cache system and build type and the last command.
     settings.cmake_system_type = system_type.to_string();
@@ -191,18 +344,211 @@ fn generate_cmake_project(settings: &mut Settings, system_type: &str, build_type
     settings.last_cmake_configuration_command = preset.clone();
     let _ = settings.save_default();
 
-    cmd::execute_and_display_output(preset);
+    let output = configure_cmake_project(settings, preset, args.reconfigure);
 
     debug!("Settings: {:#?}", settings);
+
+    output
 }
 
-fn generate_preset(
+// Makes the build tree of the variant the active build directory and returns it.
+fn select_build_variant(settings: &mut Settings, build_variant: &Variant) -> String {
+    let base_build_dir = variant::base_build_dir(&settings.build_dir, &settings.build_variant);
+    let build_dir = variant::variant_build_dir(&base_build_dir, build_variant);
+
+    if !build_variant.is_default() {
+        info!("Build variant: {} ({})", build_variant.name(), build_dir.display());
+        if let Err(e) = std::fs::create_dir_all(&build_dir) {
+            error!("Failed to create the build directory '{}': {}", build_dir.display(), e);
+        }
+    }
+
+    settings.build_dir = build_dir.to_string_lossy().to_string();
+    settings.build_variant = build_variant.name();
+    settings.build_dir.clone()
+}
+
+// Resolves the launcher ('--launcher' or the 'compiler_launcher' setting) and injects it.
+fn apply_compiler_launcher(
+    configure_command: Vec<String>,
+    system_type: &str,
+    launcher: &str
+) -> Vec<String> {
+    if launcher.trim().is_empty() {
+        return configure_command;
+    }
+    if launcher == "none" {
+        return launcher::apply(configure_command, None);
+    }
+
+    // Visual Studio generators silently ignore the launcher variables
+    if system_type == "nt/msvc" {
+        warn!(
+            "Compiler launchers are not supported by the Visual Studio generator. Skipping '{}'.",
+            launcher
+        );
+        return configure_command;
+    }
+
+    let found = if launcher == "auto" {
+        launcher::detect()
+    } else {
+        match Launcher::parse(launcher) {
+            Some(selected) => selected.find().map(|path| (selected, path)),
+            None => {
+                RuntimeErrors::InvalidLauncher(launcher.to_string()).exit();
+                None
+            }
+        }
+    };
+
+    match found {
+        Some((selected, path)) => {
+            info!("Using compiler launcher '{}': {}", selected.program(), path);
+            launcher::apply(configure_command, Some(&path))
+        }
+        None if launcher == "auto" => {
+            warn!("No compiler launcher (ccache, sccache) found on PATH. Building without one.");
+            configure_command
+        }
+        None => {
+            RuntimeErrors::LauncherNotFound(launcher.to_string()).exit();
+            configure_command
+        }
+    }
+}
+
+// Applies '--define' and '--undefine' on top of a configure command.
+fn apply_definition_overrides(configure_command: Vec<String>, args: &BuildArgs) -> Vec<String> {
+    if args.define.is_empty() && args.undefine.is_empty() {
+        return configure_command;
+    }
+
+    let mut command = ConfigureCommand::parse(&configure_command);
+    for define in &args.define {
+        match Definition::parse(define) {
+            Some(definition) => {
+                info!("Defining: {}", definition);
+                command.set_definition(definition);
+            }
+            None => RuntimeErrors::InvalidDefinition(define.clone()).exit(),
+        }
+    }
+    for key in &args.undefine {
+        info!("Undefining: {}", key);
+        command.unset_definition(key);
+    }
+    command.to_argv()
+}
+
+// Runs the configure command unless nothing that affects it changed since the last successful run.
+// Returns None if the configure was skipped.
+fn configure_cmake_project(
+    settings: &mut Settings,
+    configure_command: Vec<String>,
+    reconfigure: bool
+) -> Option<CommandOutput> {
+    let build_dir = PathBuf::from(&settings.build_dir);
+    let current = configure_fingerprint(settings, &configure_command);
+
+    let reason = if reconfigure {
+        Some("reconfigure requested".to_string())
+    } else if !build_dir.join("CMakeCache.txt").exists() {
+        Some("build tree is not configured".to_string())
+    } else if !file_api::reply_dir(&build_dir).exists() {
+        Some("CMake File API reply is missing".to_string())
+    } else {
+        fingerprint::describe_change(&settings.last_configure_fingerprint, &current)
+    };
+
+    let reason = match reason {
+        Some(reason) => reason,
+        None => {
+            info!(
+                "Configure inputs unchanged. Skipping CMake configure (use --reconfigure to force)."
+            );
+            return None;
+        }
+    };
+    info!("Running CMake configure: {}", reason);
+
+    write_file_api_query(settings);
+
+    let output = cmd::execute_and_capture_output(configure_command);
+
+    // Only a successful configure may be skipped next time
+    settings.last_configure_fingerprint = if output.status == 0 {
+        current
+    } else {
+        error!("CMake configure failed with code {}", output.status);
+        Default::default()
+    };
+    let _ = settings.save_default();
+
+    Some(output)
+}
+
+// Fingerprint of the configure inputs, compared before the next configure.
+pub fn configure_fingerprint(settings: &Settings, configure_command: &[String]) -> Fingerprint {
+    let build_dir = PathBuf::from(&settings.build_dir);
+    let dist_dir = dist_dir(settings);
+    fingerprint::compute(
+        configure_command,
+        &settings.cmake_build_type,
+        &settings.vcpkg_path,
+        Path::new(&settings.working_dir),
+        &[&build_dir, Path::new(&settings.install_dir), &dist_dir, Path::new(&settings.exe_dir)]
+    )
+}
+
+// Requests the codemodel from the CMake File API so target information is available after configure.
+fn write_file_api_query(settings: &Settings) {
+    match file_api::write_query(Path::new(&settings.build_dir)) {
+        Ok(_) => trace!("CMake File API query written to: {}", settings.build_dir),
+        Err(e) => warn!("Failed to write the CMake File API query: {}", e),
+    }
+}
+
+// Makes the compilation database available to editor tooling (clangd) in the project root.
+fn export_compile_commands(settings: &Settings, args: &BuildArgs) {
+    let mode = match ExportMode::parse(&args.compile_commands) {
+        Some(mode) => mode,
+        None => {
+            warn!(
+                "Invalid compile commands mode '{}', expected 'link', 'copy' or 'none'. Skipping export.",
+                args.compile_commands
+            );
+            return;
+        }
+    };
+
+    let extra_build_dirs: Vec<PathBuf> = args.merge_compile_commands
+        .iter()
+        .map(|dir| Path::new(&settings.working_dir).join(dir))
+        .collect();
+
+    match
+        compile_commands::export(
+            Path::new(&settings.build_dir),
+            &extra_build_dirs,
+            Path::new(&settings.working_dir),
+            mode
+        )
+    {
+        Ok(Some(path)) => info!("Compilation database exported to: {}", path.display()),
+        Ok(None) => trace!("No compilation database to export"),
+        Err(e) => warn!("Failed to export the compilation database: {}", e),
+    }
+}
+
+pub fn generate_preset(
     system_type: &str,
     source_dir: &str,
     build_dir: &str,
-    toolchain_path: &str
+    toolchain_path: &str,
+    build_variant: &Variant
 ) -> Vec<String> {
-    match system_type {
+    let mut preset = match system_type {
         "nt/msvc" => {
             vec![
                 "cmake".to_string(),
@@ -225,7 +571,8 @@ fn generate_preset(
                 "-G".to_string(),
                 "Ninja".to_string(),
                 "-DCMAKE_C_COMPILER=clang".to_string(),
-                "-DCMAKE_CXX_COMPILER=clang++".to_string()
+                "-DCMAKE_CXX_COMPILER=clang++".to_string(),
+                "-DCMAKE_EXPORT_COMPILE_COMMANDS=ON".to_string()
             ]
         }
         "unix/gcc" => {
@@ -238,7 +585,8 @@ fn generate_preset(
                 "-G".to_string(),
                 "Ninja".to_string(),
                 "-DCMAKE_C_COMPILER=gcc".to_string(),
-                "-DCMAKE_CXX_COMPILER=g++".to_string()
+                "-DCMAKE_CXX_COMPILER=g++".to_string(),
+                "-DCMAKE_EXPORT_COMPILE_COMMANDS=ON".to_string()
             ]
         }
         _ => {
@@ -246,13 +594,18 @@ fn generate_preset(
             RuntimeErrors::GenerateProjectInvalidSystemType(Some(system_type.to_string())).exit();
             vec![]
         }
-    }
+    };
+
+    // Sanitizer and coverage flags
+    preset.extend(build_variant.definitions(system_type));
+
+    preset
 }
 
-fn build_cmake_project(settings: &Settings, build_type: &str) {
+pub fn build_cmake_project(settings: &Settings, build_type: &str) -> CommandOutput {
     let build_dir = settings.build_dir.clone();
 
-    cmd::execute_and_display_output(
+    cmd::execute_and_capture_output(
         vec![
             "cmake".to_string(),
             "--build".to_string(),
@@ -260,13 +613,93 @@ fn build_cmake_project(settings: &Settings, build_type: &str) {
             "--config".to_string(),
             build_type.to_string()
         ]
-    );
+    )
+}
+
+// Mirrors the asset directories declared in the install config next to every executable of the build type.
+pub fn stage_runtime_assets(settings: &Settings, build_type: &str, mode: LinkMode) {
+    let config = match retrieve_install(Path::new(&settings.install_json_path)) {
+        Ok(config) => config,
+        Err(_) => {
+            RuntimeErrors::JSONFileNotFound(Some(settings.install_json_path.clone())).exit();
+            return;
+        }
+    };
+
+    if config.assets.is_empty() {
+        trace!("No assets declared in the install config. Skipping staging.");
+        return;
+    }
+
+    let asset_dirs: Vec<PathBuf> = config.assets
+        .iter()
+        .map(|asset| Path::new(&settings.working_dir).join(asset))
+        .collect();
+
+    // Executables may end up in different directories, stage into each of them once.
+    let mut destinations: Vec<PathBuf> = Vec::new();
+    match file_api::load_codemodel(Path::new(&settings.build_dir)) {
+        Ok(codemodel) => {
+            if let Some(configuration) = codemodel.configuration(build_type) {
+                for target in configuration.targets.iter().filter(|t| t.is_executable()) {
+                    for artifact in &target.artifacts {
+                        if let Some(parent) = codemodel.artifact_path(artifact).parent() {
+                            if !destinations.contains(&parent.to_path_buf()) {
+                                destinations.push(parent.to_path_buf());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            RuntimeErrors::FileApiReplyNotFound(Some(e.to_string())).exit();
+        }
+    }
+
+    if destinations.is_empty() {
+        warn!("No executable targets found. Skipping staging.");
+        return;
+    }
+
+    for destination in destinations {
+        match stage::stage_assets(&asset_dirs, &destination, mode) {
+            Ok(report) => {
+                info!(
+                    "Staged assets into {}: {} staged, {} up to date, {} removed, {} failed",
+                    destination.display(),
+                    report.staged,
+                    report.up_to_date,
+                    report.removed,
+                    report.failed
+                );
+            }
+            Err(e) => RuntimeErrors::StageAssetsFailed(Some(e.to_string())).exit(),
+        }
+    }
+}
+
+// Builds a single target. CMake only rebuilds it (and its dependencies) if it is out of date.
+pub fn build_cmake_target(settings: &Settings, build_type: &str, target: &str) -> CommandOutput {
+    let build_dir = settings.build_dir.clone();
+
+    cmd::execute_and_capture_output(
+        vec![
+            "cmake".to_string(),
+            "--build".to_string(),
+            build_dir.clone(),
+            "--config".to_string(),
+            build_type.to_string(),
+            "--target".to_string(),
+            target.to_string()
+        ]
+    )
 }
 
-fn install_cmake_project(settings: &Settings, build_type: &str) {
+fn install_cmake_project(settings: &Settings, build_type: &str) -> CommandOutput {
     let build_dir = settings.build_dir.clone();
 
-    cmd::execute_and_display_output(
+    cmd::execute_and_capture_output(
         vec![
             "cmake".to_string(),
             "--install".to_string(),
@@ -284,88 +717,5 @@ fn install_cmake_project(settings: &Settings, build_type: &str) {
             build_type.to_string(),
             "-v".to_string()
         ]
-    );
-}
-
-fn clean_cmake_project(settings: &Settings, what_to_clean: &str) {
-    let mut build_dir = false;
-    let mut install_dir = false;
-
-    for c in what_to_clean.chars() {
-        match c {
-            'b' => {
-                build_dir = true;
-            }
-            'i' => {
-                install_dir = true;
-            }
-            _ => {
-                error!("Invalid character '{}' in clean command", c);
-                RuntimeErrors::InvalidCleanCommand(c).exit();
-            }
-        }
-    }
-
-    if build_dir {
-        match std::fs::remove_dir_all(&settings.build_dir) {
-            Ok(_) => {
-                info!("Successfully removed the 'Build' directory.");
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                warn!("The build directory does not exist. Skipping this step.");
-            }
-            Err(e) => {
-                error!("Error removing the 'Build' directory: {}", e);
-            }
-        }
-    }
-
-    if install_dir {
-        match std::fs::remove_dir_all(&settings.install_dir) {
-            Ok(_) => {
-                info!("Successfully removed the 'Install' directory.");
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                warn!("The install directory does not exist. Skipping this step.");
-            }
-            Err(e) => {
-                error!("Error removing the 'Install' directory: {}", e);
-            }
-        }
-    }
-}
-
-// Creates symlinks for all files in a given directory recursively.
-// TODO: This needs more testing and development to be used in the project.
-#[allow(dead_code)]
-fn create_symlinks(src_dir: &Path, target_dir: &Path) -> std::io::Result<()> {
-    RuntimeErrors::NotImplemented.exit();
-
-    for entry in WalkDir::new(src_dir) {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() {
-            // Attempt to construct the target path for the symlink
-            let relative_path = match path.strip_prefix(src_dir) {
-                Ok(rel_path) => rel_path,
-                Err(e) => {
-                    // Convert the StripPrefixError to an io::Error
-                    return Err(std::io::Error::new(std::io::ErrorKind::Other, e));
-                }
-            };
-            let target_path = target_dir.join(relative_path);
-
-            // Ensure the target directory exists
-            if let Some(parent) = target_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-
-            // Create the symlink
-            match std::os::windows::fs::symlink_file(path, &target_path) {
-                Ok(_) => info!("Symlink created for {:?}", path),
-                Err(e) => error!("Failed to create symlink for {:?}: {}", path, e),
-            }
-        }
-    }
-    Ok(())
+    )
 }
@@ -4,6 +4,7 @@ use crate::commands::CacheArgs;
 use crate::errors::errors::RuntimeErrors;
 use crate::internal::settings::Settings;
 use crate::internal::cmd;
+use crate::internal::configure_command::ConfigureCommand;
 
 pub fn run(args: CacheArgs) {
     debug!("Running the Initialization command with arguments: {:#?}", args);
@@ -44,6 +45,8 @@ pub fn run(args: CacheArgs) {
             info!("Changing value of key '{}' to '{}'", cache_key[0], cache_key[1]);
             let _ = settings.set_value(&cache_key[0], cache_key[1].clone());
         }
+    } else if args.configure_command {
+        print_configure_command(&settings);
     } else if args.open_cache {
         open_cache_in_explorer(&settings);
     } else {
@@ -62,6 +65,36 @@ fn print_cache(settings: &Settings) {
     }
 }
 
+fn print_configure_command(settings: &Settings) {
+    if settings.last_cmake_configuration_command.is_empty() {
+        warn!("No CMake configure command cached. Generate the project with 'build -g' first.");
+        return;
+    }
+
+    let command = ConfigureCommand::parse(&settings.last_cmake_configuration_command);
+    let none = "None".to_string();
+
+    let mut output = String::from("Cached CMake configure command:");
+    output.push_str(&format!("\n  Program:    {}", command.program));
+    output.push_str(&format!("\n  Source dir: {}", command.source_dir.as_ref().unwrap_or(&none)));
+    output.push_str(&format!("\n  Build dir:  {}", command.build_dir.as_ref().unwrap_or(&none)));
+    output.push_str(&format!("\n  Generator:  {}", command.generator.as_ref().unwrap_or(&none)));
+    output.push_str("\n  Definitions:");
+    for definition in &command.definitions {
+        output.push_str(&format!("\n    {}", definition));
+    }
+    if !command.undefinitions.is_empty() {
+        output.push_str("\n  Undefined:");
+        for key in &command.undefinitions {
+            output.push_str(&format!("\n    {}", key));
+        }
+    }
+    if !command.other.is_empty() {
+        output.push_str(&format!("\n  Other arguments: {}", command.other.join(" ")));
+    }
+    info!("{}", output);
+}
+
 fn open_cache_in_explorer(settings: &Settings) {
     let env = &settings.os;
     let cache_path = Settings::get_settings_path().unwrap();
@@ -0,0 +1,209 @@
+use spdlog::prelude::*;
+use std::path::{ Path, PathBuf };
+use walkdir::WalkDir;
+
+use crate::commands::CleanArgs;
+use crate::commands::package::dist_dir;
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::cmd;
+
+pub fn run(args: CleanArgs) {
+    debug!("Running the Clean command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    clean_cmake_project(&settings, &args);
+}
+
+pub fn clean_cmake_project(settings: &Settings, args: &CleanArgs) {
+    let mut build_dir = false;
+    let mut install_dir = false;
+    let mut package_dir = false;
+
+    let what_to_clean = args.what_to_clean.clone().unwrap_or("bi".to_string());
+    for c in what_to_clean.chars() {
+        match c {
+            'b' => {
+                build_dir = true;
+            }
+            'i' => {
+                install_dir = true;
+            }
+            'p' => {
+                package_dir = true;
+            }
+            _ => {
+                error!("Invalid character '{}' in clean command", c);
+                RuntimeErrors::InvalidCleanCommand(c).exit();
+            }
+        }
+    }
+
+    if args.debug_build_type && args.release_build_type {
+        error!("Both debug and release build types set. Use only one.");
+        RuntimeErrors::BuildTypeBothSet.exit();
+    }
+    let build_type = if args.debug_build_type {
+        Some("Debug")
+    } else if args.release_build_type {
+        Some("Release")
+    } else {
+        None
+    };
+
+    if args.dry_run {
+        info!("Dry run. Nothing will be deleted.");
+    }
+
+    if build_dir {
+        match &args.system_type {
+            Some(system_type) if *system_type != settings.cmake_system_type => {
+                info!(
+                    "The build directory was generated for '{}', not '{}'. Skipping this step.",
+                    settings.cmake_system_type,
+                    system_type
+                );
+            }
+            _ => {
+                // A single build type can only be cleaned through CMake, the build directory is shared.
+                if args.soft || build_type.is_some() {
+                    soft_clean(settings, build_type, args.dry_run);
+                } else {
+                    remove_dir(settings, "Build", Path::new(&settings.build_dir), args.dry_run);
+                }
+            }
+        }
+    }
+
+    if install_dir {
+        let path = build_type_dir(settings, Path::new(&settings.install_dir), build_type);
+        remove_dir(settings, "Install", &path, args.dry_run);
+    }
+
+    if package_dir {
+        let path = build_type_dir(settings, &dist_dir(settings), build_type);
+        remove_dir(settings, "Dist", &path, args.dry_run);
+    }
+}
+
+// Install and Dist are laid out as '<dir>/<os_release>/<build_type>'
+fn build_type_dir(settings: &Settings, dir: &Path, build_type: Option<&str>) -> PathBuf {
+    match build_type {
+        Some(build_type) => dir.join(&settings.os_release).join(build_type),
+        None => dir.to_path_buf(),
+    }
+}
+
+fn soft_clean(settings: &Settings, build_type: Option<&str>, dry_run: bool) {
+    // Without a cache there is no generated project to clean
+    if !Path::new(&settings.build_dir).join("CMakeCache.txt").exists() {
+        warn!("The build directory has not been generated. Skipping this step.");
+        return;
+    }
+
+    let mut clean = vec![
+        "cmake".to_string(),
+        "--build".to_string(),
+        settings.build_dir.clone(),
+        "--target".to_string(),
+        "clean".to_string()
+    ];
+    if let Some(build_type) = build_type {
+        clean.extend(["--config".to_string(), build_type.to_string()]);
+    }
+
+    if dry_run {
+        info!("Would run: {}", clean.join(" "));
+        return;
+    }
+
+    cmd::execute_and_display_output(clean);
+    info!("Successfully cleaned the 'Build' directory.");
+}
+
+fn remove_dir(settings: &Settings, name: &str, path: &Path, dry_run: bool) {
+    if !path.exists() {
+        warn!("The {} directory does not exist. Skipping this step.", name);
+        return;
+    }
+
+    if let Err(reason) = check_safe_to_delete(settings, path) {
+        RuntimeErrors::UnsafeDeletePath(path.display().to_string(), reason).exit();
+    }
+
+    if dry_run {
+        let (files, bytes) = directory_size(path);
+        info!("Would remove '{}' ({} files, {} bytes)", path.display(), files, bytes);
+        return;
+    }
+
+    match std::fs::remove_dir_all(path) {
+        Ok(_) => {
+            info!("Successfully removed the '{}' directory: {}", name, path.display());
+        }
+        Err(e) => {
+            error!("Error removing the '{}' directory: {}", name, e);
+        }
+    }
+}
+
+// Guards against a misconfigured settings file pointing a generated directory at something important.
+// Returns the reason if the directory must not be deleted.
+pub fn check_safe_to_delete(settings: &Settings, path: &Path) -> Result<(), String> {
+    let path = path.canonicalize().map_err(|e| e.to_string())?;
+    let project_dir = Path::new(&settings.working_dir)
+        .canonicalize()
+        .map_err(|e| format!("project directory is not accessible: {}", e))?;
+
+    if path == project_dir {
+        return Err("it is the project directory".to_string());
+    }
+    if project_dir.starts_with(&path) {
+        return Err("it contains the project directory".to_string());
+    }
+    if !path.starts_with(&project_dir) {
+        return Err("it is outside of the project directory".to_string());
+    }
+    if let Ok(exe_dir) = Path::new(&settings.exe_dir).canonicalize() {
+        if exe_dir.starts_with(&path) {
+            return Err("it contains the CPM executable".to_string());
+        }
+    }
+    // Generated directories never contain sources
+    if path.join("CMakeLists.txt").exists() || path.join(".git").exists() {
+        return Err("it contains a source tree".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn directory_size(path: &Path) -> (usize, u64) {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .fold((0, 0), |(files, bytes), entry| {
+            (files + 1, bytes + entry.metadata().map(|m| m.len()).unwrap_or(0))
+        })
+}
@@ -0,0 +1,140 @@
+use spdlog::prelude::*;
+use std::path::Path;
+
+use crate::commands::CMakeCacheArgs;
+use crate::commands::build::configure_fingerprint;
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::cmake_cache::{ self, CacheEntry };
+use crate::internal::configure_command::{ ConfigureCommand, Definition };
+use crate::internal::cmd;
+
+pub fn run(args: CMakeCacheArgs) {
+    debug!("Running the CMake Cache command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let mut settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    let build_dir = Path::new(&settings.build_dir);
+    let entries = match cmake_cache::load(build_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!("Failed to read the CMake cache: {}", e);
+            RuntimeErrors::CMakeCacheNotFound(
+                cmake_cache::cache_path(build_dir).display().to_string()
+            ).exit();
+            return;
+        }
+    };
+
+    if let Some(key) = &args.get {
+        match entries.iter().find(|entry| entry.key == *key) {
+            Some(entry) => info!("{}", entry.value),
+            None => RuntimeErrors::CMakeCacheEntryNotFound(key.to_string()).exit(),
+        }
+    } else if let Some(assignment) = &args.set {
+        set_entry(&mut settings, &entries, assignment);
+    } else {
+        print_entries(&entries, &args);
+    }
+}
+
+fn print_entries(entries: &[CacheEntry], args: &CMakeCacheArgs) {
+    let filter = args.filter.as_ref().map(|filter| filter.to_lowercase());
+
+    let mut output = String::from("CMake cache entries:");
+    let mut count = 0;
+    for entry in entries {
+        if entry.is_internal() && !args.internal {
+            continue;
+        }
+        if entry.advanced && !args.advanced {
+            continue;
+        }
+        if let Some(filter) = &filter {
+            if !entry.key.to_lowercase().contains(filter) {
+                continue;
+            }
+        }
+
+        output.push_str(&format!("\n  {}:{}={}", entry.key, entry.entry_type, entry.value));
+        if !entry.help.is_empty() {
+            output.push_str(&format!("\n      {}", entry.help));
+        }
+        count += 1;
+    }
+
+    if count == 0 {
+        warn!("No matching CMake cache entries. Use -a/-i to include advanced/internal entries.");
+    } else {
+        info!("{}", output);
+    }
+}
+
+// Sets the entry by re-running configure on the existing build tree with '-DKEY:TYPE=VALUE'.
+fn set_entry(settings: &mut Settings, entries: &[CacheEntry], assignment: &str) {
+    let mut definition = match Definition::parse(assignment) {
+        Some(definition) => definition,
+        None => {
+            RuntimeErrors::InvalidDefinition(assignment.to_string()).exit();
+            return;
+        }
+    };
+
+    // Keep the type of an existing entry, otherwise CMake would store it as UNINITIALIZED
+    if definition.value_type.is_none() {
+        if let Some(entry) = entries.iter().find(|entry| entry.key == definition.key) {
+            if !entry.is_internal() {
+                definition.value_type = Some(entry.entry_type.clone());
+            }
+        }
+    }
+
+    info!("Setting CMake cache entry: {}", definition);
+
+    // 'cmake <existing build dir>' reads the source directory from the cache
+    let output = cmd::execute_and_capture_output(
+        vec!["cmake".to_string(), format!("-D{}", definition), settings.build_dir.clone()]
+    );
+    if output.status != 0 {
+        RuntimeErrors::CMakeCacheSetFailed(definition.to_string(), output.status).exit();
+        return;
+    }
+
+    // The cached configure command would revert the value on the next generate, update it as well.
+    // The build tree was just configured, so the next generate can be skipped until an input changes.
+    if !settings.last_cmake_configuration_command.is_empty() {
+        let mut command = ConfigureCommand::parse(&settings.last_cmake_configuration_command);
+        if command.definitions.iter().any(|d| d.key == definition.key) {
+            command.set_definition(definition.clone());
+            settings.last_cmake_configuration_command = command.to_argv();
+        }
+        settings.last_configure_fingerprint = configure_fingerprint(
+            settings,
+            &settings.last_cmake_configuration_command
+        );
+        if let Err(e) = settings.save_default() {
+            error!("Failed to save settings: {}", e);
+        }
+    }
+    info!("CMake cache entry '{}' set.", definition.key);
+}
@@ -0,0 +1,353 @@
+use spdlog::prelude::*;
+use std::path::{ Path, PathBuf };
+use walkdir::WalkDir;
+
+use crate::commands::CoverageArgs;
+use crate::commands::build::{ build_cmake_project, resolve_build_type };
+use crate::commands::info::load_codemodel;
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::cmd;
+use crate::internal::lcov::{ self, Record };
+use crate::internal::variant;
+
+pub fn run(args: CoverageArgs) {
+    debug!("Running the Coverage command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    // Only the coverage variant is instrumented
+    if !settings.build_variant.split('-').any(|part| part == "coverage") {
+        RuntimeErrors::CoverageBuildNotFound.exit();
+    }
+
+    let build_type = resolve_build_type(args.debug_build_type, args.release_build_type, &settings);
+    info!("Build Type: {}", build_type);
+
+    let output_dir = match &args.output_dir {
+        Some(output_dir) => Path::new(&settings.working_dir).join(output_dir),
+        None => Path::new(&settings.build_dir).join("coverage"),
+    };
+    if let Err(e) = std::fs::create_dir_all(&output_dir) {
+        RuntimeErrors::CoverageFailed(
+            format!("failed to create '{}': {}", output_dir.display(), e)
+        ).exit();
+    }
+
+    if args.build_project {
+        // A failed build would report the coverage of the previous build
+        let output = build_cmake_project(&settings, &build_type);
+        if output.status != 0 {
+            RuntimeErrors::BuildStepFailed("build".to_string(), output.status).exit();
+        }
+    }
+
+    let llvm = settings.cmake_system_type == "unix/clang";
+    let tracefile = output_dir.join("coverage.info");
+
+    let mut tests_status = 0;
+    if !args.no_test {
+        tests_status = run_tests(&settings, &args, &build_type, &output_dir, llvm);
+    }
+
+    if llvm {
+        collect_llvm(&settings, &build_type, &output_dir, &tracefile);
+    } else {
+        collect_gcov(&settings, &tracefile);
+    }
+
+    // Only report the sources of the project, not system headers, dependencies or generated files
+    let records: Vec<Record> = match lcov::load(&tracefile) {
+        Ok(records) => records
+            .into_iter()
+            .filter(|record| is_project_source(&settings, &record.source_file))
+            .collect(),
+        Err(e) => {
+            RuntimeErrors::CoverageFailed(
+                format!("failed to read '{}': {}", tracefile.display(), e)
+            ).exit();
+            return;
+        }
+    };
+    if let Err(e) = lcov::write(&tracefile, &records) {
+        RuntimeErrors::CoverageFailed(
+            format!("failed to write '{}': {}", tracefile.display(), e)
+        ).exit();
+    }
+    info!("lcov tracefile written to: {}", tracefile.display());
+
+    if !args.no_html {
+        generate_html(&settings, &build_type, &output_dir, &tracefile, llvm);
+    }
+
+    let total = print_summary(&settings, &records);
+
+    if tests_status != 0 {
+        RuntimeErrors::CTestFailed(tests_status).exit();
+    }
+    if let Some(minimum) = args.min {
+        if total < minimum {
+            RuntimeErrors::CoverageBelowThreshold(total, minimum).exit();
+        }
+    }
+}
+
+// Runs CTest with fresh coverage counters. Returns the CTest exit code.
+fn run_tests(
+    settings: &Settings,
+    args: &CoverageArgs,
+    build_type: &str,
+    output_dir: &Path,
+    llvm: bool
+) -> i32 {
+    let mut envs: Vec<(String, String)> = Vec::new();
+
+    if llvm {
+        // Every test process writes its own raw profile, '%p' is the process id
+        let profraw_dir = output_dir.join("profraw");
+        let _ = std::fs::remove_dir_all(&profraw_dir);
+        let _ = std::fs::create_dir_all(&profraw_dir);
+        envs.push((
+            "LLVM_PROFILE_FILE".to_string(),
+            profraw_dir.join("%p-%m.profraw").to_string_lossy().to_string(),
+        ));
+    } else {
+        // gcov accumulates counters across runs, start from zero
+        for gcda in files_with_extension(Path::new(&settings.build_dir), "gcda") {
+            let _ = std::fs::remove_file(gcda);
+        }
+    }
+
+    let mut ctest = vec![
+        "ctest".to_string(),
+        "--test-dir".to_string(),
+        settings.build_dir.clone(),
+        "-C".to_string(),
+        build_type.to_string(),
+        "--output-on-failure".to_string()
+    ];
+    if let Some(regex) = &args.tests_regex {
+        ctest.extend(["-R".to_string(), regex.clone()]);
+    }
+
+    let status = cmd::execute_interactive(ctest, envs);
+    if status != 0 {
+        warn!("Some tests failed (code {}). The coverage report is still generated.", status);
+    }
+    status
+}
+
+// gcc: lcov reads the .gcno/.gcda files next to the object files through gcov.
+fn collect_gcov(settings: &Settings, tracefile: &Path) {
+    let lcov = require_tool("lcov");
+
+    let output = cmd::execute_and_capture_output(
+        vec![
+            lcov,
+            "--capture".to_string(),
+            "--directory".to_string(),
+            settings.build_dir.clone(),
+            "--base-directory".to_string(),
+            settings.working_dir.clone(),
+            "--output-file".to_string(),
+            tracefile.to_string_lossy().to_string()
+        ]
+    );
+    if output.status != 0 {
+        RuntimeErrors::CoverageFailed(format!("lcov failed: {}", output.stderr)).exit();
+    }
+}
+
+// clang: merge the raw profiles and export them for every instrumented binary.
+fn collect_llvm(settings: &Settings, build_type: &str, output_dir: &Path, tracefile: &Path) {
+    let llvm_profdata = require_tool("llvm-profdata");
+    let llvm_cov = require_tool("llvm-cov");
+
+    let profraw_files = files_with_extension(&output_dir.join("profraw"), "profraw");
+    if profraw_files.is_empty() {
+        RuntimeErrors::CoverageFailed(
+            "no raw profiles were written, run the tests first".to_string()
+        ).exit();
+    }
+
+    let profdata = output_dir.join("coverage.profdata");
+    let mut merge = vec![llvm_profdata, "merge".to_string(), "-sparse".to_string()];
+    merge.extend(profraw_files.iter().map(|path| path.to_string_lossy().to_string()));
+    merge.extend(["-o".to_string(), profdata.to_string_lossy().to_string()]);
+    let output = cmd::execute_and_capture_output(merge);
+    if output.status != 0 {
+        RuntimeErrors::CoverageFailed(format!("llvm-profdata failed: {}", output.stderr)).exit();
+    }
+
+    let mut export = vec![
+        llvm_cov,
+        "export".to_string(),
+        "-format=lcov".to_string(),
+        format!("-instr-profile={}", profdata.to_string_lossy())
+    ];
+    export.extend(instrumented_binaries(settings, build_type));
+    let output = cmd::execute_and_capture_output(export);
+    if output.status != 0 {
+        RuntimeErrors::CoverageFailed(format!("llvm-cov failed: {}", output.stderr)).exit();
+    }
+    if let Err(e) = std::fs::write(tracefile, output.stdout) {
+        RuntimeErrors::CoverageFailed(
+            format!("failed to write '{}': {}", tracefile.display(), e)
+        ).exit();
+    }
+}
+
+// llvm-cov takes the first binary as is and every other one with '-object'.
+fn instrumented_binaries(settings: &Settings, build_type: &str) -> Vec<String> {
+    let codemodel = load_codemodel(settings);
+    let mut binaries: Vec<String> = Vec::new();
+
+    if let Some(configuration) = codemodel.configuration(build_type) {
+        for target in &configuration.targets {
+            if !(target.is_executable() || target.is_shared_library()) {
+                continue;
+            }
+            for artifact in &target.artifacts {
+                let path = codemodel.artifact_path(artifact);
+                if !path.exists() {
+                    continue;
+                }
+                if !binaries.is_empty() {
+                    binaries.push("-object".to_string());
+                }
+                binaries.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    if binaries.is_empty() {
+        RuntimeErrors::CoverageFailed(
+            "no built executables or shared libraries found, build the project first".to_string()
+        ).exit();
+    }
+    binaries
+}
+
+// Prefers genhtml, llvm-cov can render HTML itself for clang builds.
+fn generate_html(
+    settings: &Settings,
+    build_type: &str,
+    output_dir: &Path,
+    tracefile: &Path,
+    llvm: bool
+) {
+    let html_dir = output_dir.join("html");
+
+    let html = if let Some(genhtml) = cmd::find_program("genhtml") {
+        vec![
+            genhtml,
+            tracefile.to_string_lossy().to_string(),
+            "--output-directory".to_string(),
+            html_dir.to_string_lossy().to_string()
+        ]
+    } else if llvm {
+        let mut show = vec![
+            require_tool("llvm-cov"),
+            "show".to_string(),
+            "-format=html".to_string(),
+            format!("-instr-profile={}", output_dir.join("coverage.profdata").to_string_lossy()),
+            format!("-output-dir={}", html_dir.to_string_lossy())
+        ];
+        show.extend(instrumented_binaries(settings, build_type));
+        // Restrict the report to the project sources
+        show.push(settings.working_dir.clone());
+        show
+    } else {
+        warn!("genhtml not found on PATH. Skipping the HTML report.");
+        return;
+    };
+
+    let output = cmd::execute_and_capture_output(html);
+    if output.status != 0 {
+        warn!("Failed to generate the HTML report: {}", output.stderr);
+        return;
+    }
+    info!("HTML report written to: {}", html_dir.join("index.html").display());
+}
+
+// Prints the line coverage of every file and returns the total percentage.
+fn print_summary(settings: &Settings, records: &[Record]) -> f64 {
+    let mut records: Vec<&Record> = records.iter().collect();
+    records.sort_by(|a, b| a.source_file.cmp(&b.source_file));
+
+    let mut output = String::from("Line coverage:");
+    let mut found = 0;
+    let mut hit = 0;
+    for record in &records {
+        found += record.lines_found;
+        hit += record.lines_hit;
+        let relative = Path::new(&record.source_file)
+            .strip_prefix(&settings.working_dir)
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or(record.source_file.clone());
+        output.push_str(
+            &format!(
+                "\n  {:>6.2}%  {:>5}/{:<5}  {}",
+                record.percent(),
+                record.lines_hit,
+                record.lines_found,
+                relative
+            )
+        );
+    }
+
+    let total = lcov::percent(hit, found);
+    output.push_str(&format!("\n  {:>6.2}%  {:>5}/{:<5}  TOTAL", total, hit, found));
+    info!("{}", output);
+
+    total
+}
+
+fn is_project_source(settings: &Settings, source_file: &str) -> bool {
+    let path = Path::new(source_file);
+    // Also excludes the plain build tree next to the variant
+    let build_dir = variant::base_build_dir(&settings.build_dir, &settings.build_variant);
+    path.starts_with(&settings.working_dir) &&
+        !path.starts_with(&build_dir) &&
+        !path.starts_with(&settings.install_dir)
+}
+
+fn require_tool(tool: &str) -> String {
+    match cmd::find_program(tool) {
+        Some(path) => path,
+        None => {
+            RuntimeErrors::CoverageToolNotFound(tool.to_string()).exit();
+            String::new()
+        }
+    }
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().is_some_and(|e| e == extension))
+        .map(|entry| entry.into_path())
+        .collect()
+}
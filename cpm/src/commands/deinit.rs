@@ -0,0 +1,140 @@
+use spdlog::prelude::*;
+use std::path::{ Path, PathBuf };
+
+use crate::commands::DeinitArgs;
+use crate::commands::clean::{ check_safe_to_delete, directory_size };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::{ compile_commands, entrypoint, variant };
+
+pub fn run(args: DeinitArgs) {
+    debug!("Running the Deinit command with arguments: {:#?}", args);
+
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let mut settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    let project_root = PathBuf::from(&settings.working_dir);
+    let (directories, files) = if project_root.is_dir() {
+        created_paths(&settings, &project_root, args.keep_install_config)
+    } else {
+        warn!("The project directory does not exist anymore: {:?}", project_root);
+        (vec![], vec![])
+    };
+
+    // Refuse before removing anything, a half deinitialized project is worse than none
+    for (_, path) in &directories {
+        if let Err(reason) = check_safe_to_delete(&settings, path) {
+            RuntimeErrors::UnsafeDeletePath(path.display().to_string(), reason).exit();
+        }
+    }
+
+    if args.dry_run {
+        info!("Dry run. Nothing will be removed.");
+        for (_, path) in &directories {
+            let (count, bytes) = directory_size(path);
+            info!("Would remove '{}' ({} files, {} bytes)", path.display(), count, bytes);
+        }
+        for path in &files {
+            info!("Would remove '{}'", path.display());
+        }
+        info!("Would reset the project settings of {:?}", settings.working_dir);
+        return;
+    }
+
+    let mut failed = 0;
+    for (name, path) in &directories {
+        match std::fs::remove_dir_all(path) {
+            Ok(_) => info!("Successfully removed the '{}' directory: {}", name, path.display()),
+            Err(e) => {
+                error!("Error removing the '{}' directory: {}", name, e);
+                failed += 1;
+            }
+        }
+    }
+    for path in &files {
+        match std::fs::remove_file(path) {
+            Ok(_) => info!("Successfully removed: {}", path.display()),
+            Err(e) => {
+                error!("Error removing '{}': {}", path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    // The settings still point to the leftovers, so deinit can be run again
+    if failed > 0 {
+        RuntimeErrors::DeinitFailed(
+            format!("{} path(s) could not be removed, the settings were kept", failed)
+        ).exit();
+    }
+
+    let working_dir = settings.working_dir.clone();
+    settings.reset_project();
+    if let Err(e) = settings.save(&settings_path) {
+        RuntimeErrors::DeinitFailed(format!("could not save the settings: {}", e)).exit();
+    }
+    info!("Successfully deinitialized {:?}", working_dir);
+}
+
+// Directories and files of the project that were created by CPM and still exist:
+//     Build, Install and Dist directories   ---> as recorded, Build with its variants
+//     cpm, cpm.ps1 and cpm.bat              ---> only the ones generated by 'cpm init'
+//     compile_commands.json                 ---> only the link or copy exported by 'cpm build'
+//     cpm_install.json                      ---> unless kept
+fn created_paths(
+    settings: &Settings,
+    project_root: &Path,
+    keep_install_config: bool
+) -> (Vec<(&'static str, PathBuf)>, Vec<PathBuf>) {
+    // With a variant selected the build directory is '<Build>/<variant>'
+    let build_dir = if settings.build_dir.is_empty() {
+        PathBuf::new()
+    } else {
+        variant::base_build_dir(&settings.build_dir, &settings.build_variant)
+    };
+    let directories = [
+        ("Build", build_dir.clone()),
+        ("Install", PathBuf::from(&settings.install_dir)),
+        ("Dist", PathBuf::from(&settings.dist_dir)),
+    ]
+        .into_iter()
+        .filter(|(_, dir)| !dir.as_os_str().is_empty() && dir.is_dir())
+        .collect();
+
+    let mut files: Vec<PathBuf> = entrypoint::SCRIPTS
+        .iter()
+        .map(|script| project_root.join(script))
+        .filter(|path| entrypoint::is_generated(path))
+        .collect();
+
+    let database = project_root.join(compile_commands::FILE_NAME);
+    if compile_commands::is_exported(&database, &build_dir) {
+        files.push(database);
+    }
+
+    let install_config = Path::new(&settings.install_json_path);
+    let in_project = install_config.parent() == Some(project_root);
+    if !keep_install_config && in_project && install_config.is_file() {
+        files.push(install_config.to_path_buf());
+    }
+
+    (directories, files)
+}
@@ -0,0 +1,432 @@
+use serde_json::Value;
+use spdlog::prelude::*;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use crate::commands::{ IdeArgs, IdeClionArgs, IdeCommands, IdeVsArgs, IdeVscodeArgs };
+use crate::commands::build::generate_preset;
+use crate::commands::run::{ runtime_library_dirs, runtime_library_variable };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::configure_command::{ ConfigureCommand, Definition };
+use crate::internal::file_api::{ self, CodeModel };
+use crate::internal::ide_profile::{ self, CMakeProfile };
+use crate::internal::json_edit;
+use crate::internal::variant::{ self, Variant };
+use crate::internal::visual_studio::{ self, LaunchTarget };
+use crate::internal::clion;
+use crate::internal::vscode::{ self, Executable };
+
+const SYSTEM_TYPES: [&str; 3] = ["nt/msvc", "unix/clang", "unix/gcc"];
+
+pub fn run(args: IdeArgs) {
+    debug!("Running the Ide command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    match &args.command {
+        IdeCommands::Vscode(vscode_args) => generate_vscode(&settings, vscode_args),
+        IdeCommands::Vs(vs_args) => generate_visual_studio(&settings, vs_args),
+        IdeCommands::Clion(clion_args) => generate_clion(&settings, clion_args),
+    }
+}
+
+fn generate_vscode(settings: &Settings, args: &IdeVscodeArgs) {
+    let project_root = Path::new(&settings.working_dir);
+    let vscode_dir = project_root.join(vscode::VSCODE_DIR);
+    if let Err(e) = fs::create_dir_all(&vscode_dir) {
+        error!("Error creating the '{}' directory: {}", vscode_dir.display(), e);
+        return;
+    }
+
+    let system_type = settings.cmake_system_type.as_str();
+    if system_type.is_empty() {
+        warn!(
+            "The project has not been generated yet, the configure tasks replay the last configure command. Generate it first with 'cpm build -d -g <SYSTEM_TYPE>'."
+        );
+    }
+
+    let program = vscode::workspace_path(project_root, Path::new(&settings.exe_path));
+    let tasks = vscode::tasks(&program, system_type, &default_build_type(settings));
+    update_document(&vscode_dir.join("tasks.json"), args.force, |document| {
+        vscode::merge_tasks(document, tasks)
+    });
+
+    match executables(settings, project_root) {
+        Some(executables) => {
+            if executables.is_empty() {
+                warn!("The project has no executable targets. Skipping the launch configurations.");
+            } else {
+                let configurations = vscode::launch_configurations(&executables, system_type);
+                update_document(&vscode_dir.join("launch.json"), args.force, |document| {
+                    vscode::merge_launch(document, configurations)
+                });
+            }
+        }
+        None => {
+            warn!(
+                "No CMake code model found, skipping the launch configurations. Generate the project ('cpm build -d -g <SYSTEM_TYPE>') and run this command again."
+            );
+        }
+    }
+
+    if system_type == "nt/msvc" {
+        warn!(
+            "The Visual Studio generator does not write compile_commands.json, IntelliSense only gets the include path. Generate with 'unix/clang' or 'unix/gcc' for a compile database."
+        );
+    }
+    let configuration = vscode::cpp_configuration(system_type, &settings.os);
+    update_document(&vscode_dir.join("c_cpp_properties.json"), args.force, |document| {
+        vscode::merge_cpp_properties(document, configuration)
+    });
+    update_document(&vscode_dir.join("settings.json"), args.force, vscode::merge_settings);
+}
+
+fn generate_visual_studio(settings: &Settings, args: &IdeVsArgs) {
+    let project_root = Path::new(&settings.working_dir);
+    let profiles = profiles(settings, project_root, "vs");
+    if profiles.is_empty() {
+        return;
+    }
+
+    let configurations = visual_studio::cmake_settings_configurations(&profiles, project_root);
+    let cmake_settings = project_root.join(visual_studio::CMAKE_SETTINGS_FILE);
+    update_document(&cmake_settings, args.force, |document| {
+        visual_studio::merge_cmake_settings(document, configurations)
+    });
+
+    match launch_targets(settings) {
+        Some(targets) => {
+            if targets.is_empty() {
+                warn!("The project has no executable targets. Skipping the launch configurations.");
+            } else {
+                let configurations = visual_studio::launch_configurations(&targets);
+                let launch = project_root.join(visual_studio::LAUNCH_FILE);
+                update_document(&launch, args.force, |document| {
+                    visual_studio::merge_launch(document, configurations)
+                });
+            }
+        }
+        None => {
+            warn!(
+                "No CMake code model found, skipping the launch configurations. Generate the project ('cpm build -d -g <SYSTEM_TYPE>') and run this command again."
+            );
+        }
+    }
+}
+
+fn generate_clion(settings: &Settings, args: &IdeClionArgs) {
+    let project_root = Path::new(&settings.working_dir);
+    let profiles = profiles(settings, project_root, "clion");
+    if profiles.is_empty() {
+        return;
+    }
+
+    let idea_dir = project_root.join(clion::IDEA_DIR);
+    if let Err(e) = fs::create_dir_all(&idea_dir) {
+        error!("Error creating the '{}' directory: {}", idea_dir.display(), e);
+        return;
+    }
+
+    let path = idea_dir.join(clion::CMAKE_FILE);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            error!("Error reading '{}': {}", path.display(), e);
+            return;
+        }
+    };
+
+    let updated = if contents.trim().is_empty() {
+        clion::document(&profiles, project_root)
+    } else {
+        match clion::merge(&contents, &profiles, project_root) {
+            Some(updated) => updated,
+            None if args.force => {
+                warn!("Overwriting '{}', it has no CMake profiles list.", path.display());
+                clion::document(&profiles, project_root)
+            }
+            None => {
+                warn!(
+                    "Skipping '{}', it has no CMake profiles list. Use --force to overwrite it.",
+                    path.display()
+                );
+                return;
+            }
+        }
+    };
+
+    match fs::write(&path, updated) {
+        Ok(_) => info!("Successfully wrote: {}", path.display()),
+        Err(e) => error!("Error writing '{}': {}", path.display(), e),
+    }
+}
+
+// A profile per system type and build type. The generated system type replays the last configure
+// command (with its definition overrides), the other ones start from the 'build -g' presets.
+// Profiles build in '<build_dir>/<ide>/<id>', next to CPM's own build tree.
+fn profiles(settings: &Settings, project_root: &Path, ide: &str) -> Vec<CMakeProfile> {
+    let system_types: Vec<&str> = if settings.cmake_system_type.is_empty() {
+        SYSTEM_TYPES.to_vec()
+    } else {
+        vec![settings.cmake_system_type.as_str()]
+    };
+    let base_build_dir = variant::base_build_dir(&settings.build_dir, &settings.build_variant);
+    let source_dir = project_root.to_string_lossy().to_string();
+    let mut profiles: Vec<CMakeProfile> = Vec::new();
+
+    for system_type in system_types {
+        if system_type == "nt/msvc" && settings.vcpkg_path.is_empty() {
+            warn!(
+                "Skipping the 'nt/msvc' profiles, the toolchain (VCPKG) path is not set. Set it with 'setup --toolchain <path>' or 'setup -a'."
+            );
+            continue;
+        }
+
+        // The cached command of a sanitizer or coverage variant would leak into every profile
+        let replay =
+            system_type == settings.cmake_system_type &&
+            settings.build_variant.is_empty() &&
+            !settings.last_cmake_configuration_command.is_empty();
+        let argv = if replay {
+            settings.last_cmake_configuration_command.clone()
+        } else {
+            generate_preset(system_type, &source_dir, "", &settings.vcpkg_path, &Variant::default())
+        };
+
+        let mut command = ConfigureCommand::parse(&argv);
+        command.definitions.retain(|definition| definition.key != "CMAKE_BUILD_TYPE");
+        if !settings.vcpkg_path.is_empty() && system_type == "nt/msvc" {
+            command.set_definition(Definition {
+                key: "CMAKE_TOOLCHAIN_FILE".to_string(),
+                value_type: None,
+                value: settings.vcpkg_path.clone(),
+            });
+        }
+
+        for build_type in ide_profile::BUILD_TYPES {
+            let id = format!("{}-{}", ide_profile::compiler_name(system_type), build_type);
+            profiles.push(CMakeProfile {
+                system_type: system_type.to_string(),
+                build_type: build_type.to_string(),
+                generator: ide_profile::GENERATOR.to_string(),
+                definitions: command.definitions.clone(),
+                build_dir: base_build_dir.join(ide).join(&id),
+                install_dir: Path::new(&settings.install_dir)
+                    .join(&settings.os_release)
+                    .join(build_type),
+                id,
+            });
+        }
+    }
+
+    if profiles.is_empty() {
+        warn!("No system type is available, no profiles were written.");
+    }
+    profiles
+}
+
+// Executable targets for launch.vs.json, None when the project was not generated. Paths are
+// relative to the profile's build root, which has the layout of CPM's build tree without the
+// configuration directories of the Visual Studio generator.
+fn launch_targets(settings: &Settings) -> Option<Vec<LaunchTarget>> {
+    let codemodel = match file_api::load_codemodel(Path::new(&settings.build_dir)) {
+        Ok(codemodel) => codemodel,
+        Err(e) => {
+            debug!("Failed to load the code model: {}", e);
+            return None;
+        }
+    };
+
+    let build_type = default_build_type(settings);
+    let Some(configuration) = codemodel
+        .configuration(&build_type)
+        .or(codemodel.configurations.first()) else {
+        return Some(vec![]);
+    };
+
+    // launch.vs.json is shared by the profiles, the default build type's libraries are used
+    let library_dirs: Vec<String> = runtime_library_dirs(settings, &codemodel, &build_type)
+        .iter()
+        .map(|dir| match build_root_path(&codemodel, dir) {
+            Some(relative) if relative.is_empty() => "${cmake.buildRoot}".to_string(),
+            Some(relative) => format!("${{cmake.buildRoot}}\\{}", relative),
+            None => dir.to_string_lossy().replace('/', "\\"),
+        })
+        .collect();
+
+    let mut targets: Vec<LaunchTarget> = Vec::new();
+    for target in configuration.targets.iter().filter(|target| target.is_executable()) {
+        let Some(artifact) = target.artifacts.first() else {
+            continue;
+        };
+        let path = codemodel.artifact_path(artifact);
+        let (Some(file_name), Some(relative_path)) = (
+            path.file_name(),
+            build_root_path(&codemodel, &path),
+        ) else {
+            continue;
+        };
+        targets.push(LaunchTarget {
+            name: target.name.clone(),
+            file_name: file_name.to_string_lossy().to_string(),
+            relative_path,
+            library_dirs: library_dirs.clone(),
+        });
+    }
+
+    Some(targets)
+}
+
+// Path inside the build tree relative to it, with '\' and without configuration directories
+// ('Debug', 'Release', ...). None for paths outside of it.
+fn build_root_path(codemodel: &CodeModel, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(&codemodel.build_dir).ok()?;
+    let components: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .filter(|component| {
+            !codemodel.configurations.iter().any(|configuration| configuration.name == *component)
+        })
+        .collect();
+    Some(components.join("\\"))
+}
+
+// Executable targets of every configuration in the code model, None when it was not generated.
+fn executables(settings: &Settings, project_root: &Path) -> Option<Vec<Executable>> {
+    let codemodel = match file_api::load_codemodel(Path::new(&settings.build_dir)) {
+        Ok(codemodel) => codemodel,
+        Err(e) => {
+            debug!("Failed to load the code model: {}", e);
+            return None;
+        }
+    };
+
+    let variable = runtime_library_variable(settings);
+    let separator = if settings.os == "windows" { ";" } else { ":" };
+    let mut executables: Vec<Executable> = Vec::new();
+
+    for configuration in &codemodel.configurations {
+        // Single-config generators without CMAKE_BUILD_TYPE report an unnamed configuration
+        let build_type = if configuration.name.is_empty() {
+            default_build_type(settings)
+        } else {
+            configuration.name.clone()
+        };
+
+        // The debugger does not get the PATH additions of 'cpm run', so they are set per configuration
+        let library_dirs: Vec<PathBuf> = runtime_library_dirs(settings, &codemodel, &build_type);
+        let environment = if library_dirs.is_empty() {
+            vec![]
+        } else {
+            let mut paths: Vec<String> = library_dirs
+                .iter()
+                .map(|dir| vscode::workspace_path(project_root, dir))
+                .collect();
+            paths.push(format!("${{env:{}}}", variable));
+            vec![(variable.to_string(), paths.join(separator))]
+        };
+
+        for target in configuration.targets.iter().filter(|target| target.is_executable()) {
+            let Some(artifact) = target.artifacts.first() else {
+                continue;
+            };
+            executables.push(Executable {
+                name: target.name.clone(),
+                build_type: build_type.clone(),
+                program: vscode::workspace_path(project_root, &codemodel.artifact_path(artifact)),
+                environment: environment.clone(),
+            });
+        }
+    }
+
+    Some(executables)
+}
+
+fn default_build_type(settings: &Settings) -> String {
+    if settings.cmake_build_type.is_empty() {
+        "Debug".to_string()
+    } else {
+        settings.cmake_build_type.clone()
+    }
+}
+
+// Applies the update to the JSON (with comments) document and writes it back with the file's
+// indentation and line endings. Missing files start empty, files that can not be parsed or have
+// comments are only replaced with --force.
+fn update_document(path: &Path, force: bool, update: impl FnOnce(Value) -> Value) {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            error!("Error reading '{}': {}", path.display(), e);
+            return;
+        }
+    };
+
+    let document = if contents.trim().is_empty() {
+        Value::Null
+    } else {
+        match serde_json::from_str::<Value>(&vscode::strip_comments(&contents)) {
+            Ok(document) => document,
+            Err(e) if force => {
+                warn!("Overwriting '{}', it could not be parsed: {}", path.display(), e);
+                Value::Null
+            }
+            Err(e) => {
+                warn!(
+                    "Skipping '{}', it could not be parsed: {}. Use --force to overwrite it.",
+                    path.display(),
+                    e
+                );
+                return;
+            }
+        }
+    };
+
+    // The rewritten file would lose them
+    if vscode::has_comments(&contents) {
+        if !force {
+            warn!(
+                "Skipping '{}', its comments would be lost. Use --force to overwrite it.",
+                path.display()
+            );
+            return;
+        }
+        warn!("Overwriting '{}', its comments are not kept.", path.display());
+    }
+
+    let serialized = match json_edit::to_string_like(&update(document), &contents) {
+        Ok(serialized) => serialized,
+        Err(e) => {
+            error!("Error serializing '{}': {}", path.display(), e);
+            return;
+        }
+    };
+
+    match fs::write(path, serialized) {
+        Ok(_) => info!("Successfully wrote: {}", path.display()),
+        Err(e) => error!("Error writing '{}': {}", path.display(), e),
+    }
+}
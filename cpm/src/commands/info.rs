@@ -0,0 +1,96 @@
+use spdlog::prelude::*;
+use std::path::Path;
+
+use crate::commands::{ InfoArgs, InfoCommands, InfoTargetsArgs };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::file_api::{ self, CodeModel };
+
+pub fn run(args: InfoArgs) {
+    debug!("Running the Info command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    match args.command {
+        InfoCommands::Targets(targets_args) => print_targets(&settings, &targets_args),
+    }
+}
+
+// Loads the codemodel of the active build directory or exits with a hint to generate the project.
+pub fn load_codemodel(settings: &Settings) -> CodeModel {
+    match file_api::load_codemodel(Path::new(&settings.build_dir)) {
+        Ok(codemodel) => codemodel,
+        Err(e) => {
+            RuntimeErrors::FileApiReplyNotFound(Some(e.to_string())).exit();
+            unreachable!()
+        }
+    }
+}
+
+fn print_targets(settings: &Settings, args: &InfoTargetsArgs) {
+    let codemodel = load_codemodel(settings);
+
+    let build_type = args.config.clone().unwrap_or(settings.cmake_build_type.clone());
+    let configuration = match codemodel.configuration(&build_type) {
+        Some(configuration) => configuration,
+        None => {
+            RuntimeErrors::ConfigurationNotFound(build_type).exit();
+            return;
+        }
+    };
+
+    let targets: Vec<_> = match &args.target {
+        Some(name) =>
+            match configuration.target(name) {
+                Some(target) => vec![target],
+                None => {
+                    RuntimeErrors::TargetNotFound(name.to_string()).exit();
+                    return;
+                }
+            }
+        None => configuration.targets.iter().collect(),
+    };
+
+    let mut output = format!(
+        "Targets ({}) in {:?}:",
+        if configuration.name.is_empty() { "default" } else { &configuration.name },
+        codemodel.source_dir
+    );
+    for target in targets {
+        output.push_str(&format!("\n  {} [{}]", target.name, target.target_type));
+        for artifact in &target.artifacts {
+            output.push_str(
+                &format!("\n    artifact: {}", codemodel.artifact_path(artifact).display())
+            );
+        }
+        if !target.dependencies.is_empty() {
+            output.push_str(&format!("\n    depends on: {}", target.dependencies.join(", ")));
+        }
+        if args.sources {
+            for source in &target.sources {
+                output.push_str(&format!("\n    source: {}", source));
+            }
+        }
+    }
+    info!("{}", output);
+}
@@ -1,30 +1,71 @@
 use spdlog::prelude::*;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{ Path, PathBuf };
 
 use crate::commands::InitArgs;
 use crate::errors::errors::RuntimeErrors;
 use crate::internal::settings::Settings;
 use crate::internal::install::Presets;
+use crate::internal::entrypoint;
+use crate::internal::project_root;
+use crate::internal::{ editor_config, gitignore };
 
 pub fn run(args: InitArgs, _no_init: bool) {
     debug!("Running the Initialization command with arguments: {:#?}", args);
     debug!("No init flag: {}", _no_init);
 
+    // Regenerating the entrypoint does not initialize anything, so it is allowed through the entrypoint
+    if args.entrypoint_only {
+        let settings = match Settings::get_settings_path().and_then(|path| Settings::load(&path)) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Failed to load settings: {}", e);
+                return;
+            }
+        };
+        if !settings.initialized {
+            RuntimeErrors::NotInitialized.exit();
+        }
+        create_entrypoint(&settings);
+        return;
+    }
+
     if _no_init {
         RuntimeErrors::NoInitFlagSet.exit();
     }
 
-    _ = entry();
+    // Checked first, so a typo does not leave a half done initialization
+    let editor_files = match editor_config::resolve(&args.editor_config) {
+        Ok(files) => files,
+        Err(name) => {
+            RuntimeErrors::InvalidEditorConfig(name).exit();
+            return;
+        }
+    };
+
+    _ = entry(args.repair);
+
+    if !editor_files.is_empty() {
+        write_editor_config(&editor_files);
+    }
 }
 
-fn entry() -> std::io::Result<()> {
+fn entry(repair: bool) -> std::io::Result<()> {
     let mut settings = Settings::load(&Settings::get_settings_path()?)?;
 
     debug!("Before:\n{:#?}", settings);
 
-    settings.working_dir = std::env::current_dir()?.to_str().unwrap().to_string();
+    // Run from a subdirectory, the project root is initialized
+    let current_dir = project_root::current()?.to_str().unwrap().to_string();
+    if Path::new(&current_dir) != std::env::current_dir()? {
+        info!("Found the project root: {:?}", current_dir);
+    }
+    if settings.initialized {
+        return reinit(&mut settings, current_dir, repair);
+    }
+
+    settings.working_dir = current_dir;
     // If working directory is the same as the executable directory, throw an error
     if settings.working_dir == settings.exe_dir {
         RuntimeErrors::WorkingDirSameAsExePath(
@@ -47,6 +88,117 @@ fn entry() -> std::io::Result<()> {
     Ok(())
 }
 
+// An initialized project is never overwritten. The differences with a fresh initialization are
+// reported, '--repair' recreates the missing pieces. A moved project (the recorded directory no longer
+// exists) is repaired in the current directory.
+fn reinit(settings: &mut Settings, current_dir: String, repair: bool) -> std::io::Result<()> {
+    let working_dir = settings.working_dir.clone();
+    let moved = !Path::new(&working_dir).exists();
+    if Path::new(&current_dir) != Path::new(&working_dir) && !moved {
+        RuntimeErrors::InitializedElsewhere(working_dir.clone(), current_dir.clone()).exit();
+    }
+    if current_dir == settings.exe_dir {
+        let exe_dir = settings.exe_dir.clone();
+        RuntimeErrors::WorkingDirSameAsExePath(current_dir.clone(), exe_dir).exit();
+    }
+
+    let differences = differences(settings, &current_dir);
+    if differences.is_empty() {
+        info!("CPM is already initialized in {:?}. Nothing to do.", settings.working_dir);
+        return Ok(());
+    }
+
+    for difference in &differences {
+        warn!("{}", difference);
+    }
+    if !repair {
+        info!("CPM is already initialized. Run 'cpm init --repair' to fix the differences above.");
+        return Ok(());
+    }
+
+    // Directories inside the moved project keep their place in it
+    if moved {
+        for dir in [&mut settings.build_dir, &mut settings.install_dir, &mut settings.dist_dir] {
+            if let Ok(relative) = Path::new(dir.as_str()).strip_prefix(&working_dir) {
+                *dir = Path::new(&current_dir).join(relative).to_str().unwrap().to_string();
+            }
+        }
+    }
+
+    settings.working_dir = current_dir;
+    os_specific(settings);
+    settings.save(&Settings::get_settings_path()?)?;
+    info!("Successfully repaired the initialization of {:?}", settings.working_dir);
+
+    debug!("After:\n{:#?}", settings);
+
+    Ok(())
+}
+
+// What 'init --repair' would change in the current directory.
+fn differences(settings: &Settings, current_dir: &str) -> Vec<String> {
+    let mut differences: Vec<String> = Vec::new();
+    if Path::new(current_dir) != Path::new(&settings.working_dir) {
+        differences.push(
+            format!("The project was moved from {:?} to {:?}", settings.working_dir, current_dir)
+        );
+    }
+
+    let directories = [
+        ("Build", &settings.build_dir, true),
+        ("Install", &settings.install_dir, true),
+        ("Dist", &settings.dist_dir, false),
+    ];
+    for (name, recorded, created) in directories {
+        let expected = project_dir(Path::new(current_dir), recorded, name);
+        if Path::new(recorded) != expected {
+            let message = format!("The '{}' directory is not in the project: {:?}", name, recorded);
+            differences.push(message);
+        } else if created && !expected.is_dir() {
+            differences.push(format!("The '{}' directory is missing: {:?}", name, expected));
+        }
+    }
+
+    let config_path = Path::new(current_dir).join("cpm_install.json");
+    if !config_path.exists() {
+        differences.push(format!("The install config is missing: {:?}", config_path));
+    } else if Path::new(&settings.install_json_path) != config_path {
+        differences.push(
+            format!("The install config is not the project's one: {:?}", settings.install_json_path)
+        );
+    }
+
+    let gitignore_path = gitignore::file_path(Path::new(current_dir));
+    let gitignore = std::fs::read_to_string(&gitignore_path).unwrap_or_default();
+    let entries = gitignore::entries(settings, Path::new(current_dir));
+    let missing = gitignore::missing(&gitignore, &entries);
+    if !missing.is_empty() {
+        differences.push(format!("{:?} does not ignore: {}", gitignore_path, missing.join(", ")));
+    }
+
+    match entrypoint::outdated(Path::new(current_dir), Path::new(&settings.exe_path)) {
+        Ok(outdated) => {
+            for path in outdated {
+                differences.push(format!("The entrypoint is missing or outdated: {:?}", path));
+            }
+        }
+        Err(e) => error!("Error checking the entrypoint files: {}", e),
+    }
+
+    differences
+}
+
+// The recorded directory is kept when it is inside the project (i.e. changed with 'cpm cache'),
+// otherwise it is '<project>/<name>'.
+fn project_dir(project_root: &Path, recorded: &str, name: &str) -> PathBuf {
+    let recorded = Path::new(recorded);
+    if recorded.starts_with(project_root) && recorded != project_root {
+        recorded.to_path_buf()
+    } else {
+        project_root.join(name)
+    }
+}
+
 fn os_specific(settings: &mut Settings) {
     let env = &settings.os;
 
@@ -60,14 +212,16 @@ fn os_specific(settings: &mut Settings) {
 
 fn windows(settings: &mut Settings) {
     get_and_load_preset_config(settings);
-    create_entrypoint();
+    create_entrypoint(settings);
     set_build_dir(settings);
     set_install_dir(settings);
+    set_dist_dir(settings);
+    update_gitignore(settings);
 }
 
 fn set_build_dir(settings: &mut Settings) {
     // Create a build directory in the working directory, check if it exists first, then save the path to the settings file.
-    let build_dir = Path::new(&settings.working_dir).join("Build");
+    let build_dir = project_dir(Path::new(&settings.working_dir), &settings.build_dir, "Build");
     settings.build_dir = build_dir.to_str().unwrap().to_string();
     // Create the build directory. If it already exists, it will just skip this step.
     std::fs::create_dir(&settings.build_dir).unwrap_or_else(|e| {
@@ -81,7 +235,11 @@ fn set_build_dir(settings: &mut Settings) {
 
 fn set_install_dir(settings: &mut Settings) {
     // Create an install directory in the working directory, check if it exists first, then save the path to the settings file.
-    let install_dir = Path::new(&settings.working_dir).join("Install");
+    let install_dir = project_dir(
+        Path::new(&settings.working_dir),
+        &settings.install_dir,
+        "Install"
+    );
     settings.install_dir = install_dir.to_str().unwrap().to_string();
     // Create the install directory
     std::fs::create_dir(&settings.install_dir).unwrap_or_else(|e| {
@@ -93,42 +251,72 @@ fn set_install_dir(settings: &mut Settings) {
     });
 }
 
-fn create_entrypoint() {
+fn set_dist_dir(settings: &mut Settings) {
+    // The distribution directory is only created when packaging, just save the path to the settings file.
+    let dist_dir = project_dir(Path::new(&settings.working_dir), &settings.dist_dir, "Dist");
+    settings.dist_dir = dist_dir.to_str().unwrap().to_string();
+}
+
+fn create_entrypoint(settings: &Settings) {
     /*
-    Create an appropriate file for an os to have an entry point at the project location.
-    i.e. on windows it would be a .bat file, on linux it would be a .sh file, etc.
-    It's a very simple file which we can just write to disk.
-    Example:
+    Create the entry point scripts at the project location, so CPM can be run as './cpm' from the project root:
+        cpm      ---> POSIX sh
+        cpm.ps1  ---> PowerShell
+        cpm.bat  ---> cmd.exe
+    Example (cpm.bat):
         @echo off
-        <path to exe> %*
-    The path to the exe is already stored in the settings file.
-    The location of this entrypoint file should be the same as the working directory (just like 'cpm_install.json').
+        "%~dp0CPM\cpm.exe" --no-init %*
+    The path to the exe is already stored in the settings file, it is written relative to the project if possible.
+    The location of the entrypoint files should be the same as the working directory (just like 'cpm_install.json').
     Also, it's important to set the no_init flag to true for the entrypoint.
     */
-    let settings = Settings::init(false).unwrap();
-    let env = settings.os;
+    let project_root = Path::new(&settings.working_dir);
+    match entrypoint::write(project_root, Path::new(&settings.exe_path)) {
+        Ok(written) => {
+            for path in written {
+                info!("Successfully wrote the entrypoint file to disk: {:?}", path.to_str().unwrap());
+            }
+        }
+        Err(e) => error!("Error writing the entrypoint files to disk: {}", e),
+    }
+}
 
-    match env.as_str() {
-        "linux" => RuntimeErrors::NotSupportedOS(Some(env.to_string())).exit(),
-        "macos" => RuntimeErrors::NotSupportedOS(Some(env.to_string())).exit(),
-        "windows" => {
-            let entrypoint_path = Path::new(&settings.working_dir).join("cpm.bat");
-            let entrypoint_content = format!("@echo off\n{} --no-init %*", settings.exe_path);
-            match File::create(entrypoint_path.clone()) {
-                Ok(mut file) => {
-                    match file.write_all(entrypoint_content.as_bytes()) {
-                        Ok(_) =>
-                            info!(
-                                "Successfully wrote the entrypoint file to disk: {:?}",
-                                entrypoint_path.clone().to_str().unwrap()
-                            ),
-                        Err(e) => error!("Error writing the entrypoint file to disk: {}", e),
-                    }
-                }
-                Err(e) => error!("Error creating the entrypoint file: {}", e),
+fn update_gitignore(settings: &Settings) {
+    // Keep the generated directories, entrypoints, ... out of version control. Entries already listed are kept.
+    let project_root = Path::new(&settings.working_dir);
+    let entries = gitignore::entries(settings, project_root);
+    match gitignore::write(project_root, &entries) {
+        Ok(added) if added.is_empty() => {
+            info!("The '.gitignore' file already ignores the generated files. Skipping this step.");
+        }
+        Ok(added) => info!("Added to '.gitignore': {}", added.join(", ")),
+        Err(e) => error!("Error updating the '.gitignore' file: {}", e),
+    }
+}
+
+fn write_editor_config(files: &[&str]) {
+    let settings = match Settings::get_settings_path().and_then(|path| Settings::load(&path)) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+    if !settings.initialized {
+        error!("The project is not initialized. Skipping the editor configuration files.");
+        return;
+    }
+
+    match editor_config::write(Path::new(&settings.working_dir), files) {
+        Ok((written, skipped)) => {
+            for path in written {
+                info!("Successfully wrote the editor configuration file: {}", path.display());
+            }
+            for path in skipped {
+                warn!("The file {} already exists. Skipping this step.", path.display());
             }
         }
-        _ => RuntimeErrors::NotSupportedOS(Some(env.to_string())).exit(),
+        Err(e) => error!("Error writing the editor configuration files: {}", e),
     }
 }
 
@@ -4,6 +4,20 @@ pub mod init;
 pub mod build;
 pub mod cache;
 pub mod setup;
+pub mod info;
+pub mod run;
+pub mod test;
+pub mod package;
+pub mod clean;
+pub mod cmake_cache;
+pub mod stats;
+pub mod coverage;
+pub mod new;
+pub mod templates;
+pub mod add;
+pub mod remove;
+pub mod deinit;
+pub mod ide;
 
 #[derive(Parser)]
 pub enum Commands {
@@ -15,10 +29,63 @@ pub enum Commands {
     Build(BuildArgs),
     /// Manage CPM Cache
     Cache(CacheArgs),
+    /// Inspect the generated CMake project
+    Info(InfoArgs),
+    /// Build and run an executable target
+    Run(RunArgs),
+    /// Run the project tests with CTest
+    Test(TestArgs),
+    /// Package the project with CPack
+    Package(PackageArgs),
+    /// Clean generated directories of the project
+    Clean(CleanArgs),
+    /// Inspect and edit the CMake cache (CMakeCache.txt) of the build directory
+    CmakeCache(CMakeCacheArgs),
+    /// Show build statistics
+    Stats(StatsArgs),
+    /// Run the tests of a coverage build and report the line coverage
+    Coverage(CoverageArgs),
+    /// Create a new CMake project from a template
+    New(NewArgs),
+    /// List the project templates
+    Templates(TemplatesArgs),
+    /// Add a target or a package to the project
+    Add(AddArgs),
+    /// Remove a package from the project
+    Remove(RemoveArgs),
+    /// Remove everything CPM created in the project and reset its settings
+    Deinit(DeinitArgs),
+    /// Generate IDE workspace files from the project settings
+    Ide(IdeArgs),
 }
 
 #[derive(Parser, Debug)]
-pub struct InitArgs {}
+pub struct InitArgs {
+    /// Only (re)write the entrypoint scripts (cpm, cpm.ps1 and cpm.bat) of the initialized project.
+    /// Can be run through the entrypoint, i.e. after moving the project or the CPM executable.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub entrypoint_only: bool,
+
+    /// Recreate the missing pieces of an initialized project (Build/Install directories, entrypoint scripts, .gitignore entries,
+    /// cpm_install.json). Without it, 'init' only reports them. A moved project is repaired in the current directory.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub repair: bool,
+
+    /// Also write editor configuration files from the embedded presets (comma separated). Existing files are kept.
+    /// FILES:
+    ///     clang-format  ---> .clang-format
+    ///     clang-tidy    ---> .clang-tidy
+    ///     editorconfig  ---> .editorconfig
+    ///     all           ---> All of the above
+    #[clap(
+        required = false,
+        long,
+        value_delimiter = ',',
+        value_names = &["FILES"],
+        verbatim_doc_comment
+    )]
+    pub editor_config: Vec<String>,
+}
 
 #[derive(Parser, Debug)]
 pub struct SetupArgs {
@@ -98,6 +165,65 @@ pub struct BuildArgs {
     )]
     pub generate_project: Option<Option<String>>,
 
+    /// Define a CMake cache variable when generating (repeatable). Format: KEY=VALUE or KEY:TYPE=VALUE.
+    /// Applied on top of the generated or last cached configure command, the merged command is cached.
+    /// Example: cpm build -d -g -D BUILD_TESTING=OFF -D CMAKE_CXX_STANDARD=20
+    #[clap(
+        required = false,
+        long,
+        short = 'D',
+        action = clap::ArgAction::Append,
+        value_names = &["KEY=VALUE"],
+        verbatim_doc_comment
+    )]
+    pub define: Vec<String>,
+
+    /// Remove a CMake cache variable when generating (repeatable).
+    /// Applied on top of the generated or last cached configure command, the merged command is cached.
+    #[clap(
+        required = false,
+        long,
+        short = 'U',
+        action = clap::ArgAction::Append,
+        value_names = &["KEY"],
+        verbatim_doc_comment
+    )]
+    pub undefine: Vec<String>,
+
+    /// Generate a sanitizer build variant (comma separated) into its own build directory '<build_dir>/<variant>'.
+    /// The variant becomes the active build directory until the project is generated without it.
+    /// Sanitizers:
+    ///     address     ---> AddressSanitizer (unix/clang, unix/gcc, nt/msvc Release only)
+    ///     undefined   ---> UndefinedBehaviorSanitizer (unix/clang, unix/gcc)
+    ///     thread      ---> ThreadSanitizer (unix/clang, unix/gcc), can not be combined
+    ///     memory      ---> MemorySanitizer (unix/clang), can not be combined
+    ///     leak        ---> LeakSanitizer (unix/clang, unix/gcc)
+    /// Example: cpm build -d -g unix/clang --sanitize address,undefined -b
+    #[clap(required = false, long, value_names = &["SANITIZERS"], verbatim_doc_comment)]
+    pub sanitize: Option<String>,
+
+    /// Generate a coverage build variant into its own build directory (can be combined with --sanitize).
+    /// unix/gcc uses gcov instrumentation, unix/clang uses source based coverage (llvm-cov).
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub coverage: bool,
+
+    /// Compiler launcher (compiler cache) to use when generating. Overrides the 'compiler_launcher' setting.
+    /// Injects CMAKE_C_COMPILER_LAUNCHER and CMAKE_CXX_COMPILER_LAUNCHER into the configure command.
+    /// Only supported by the Ninja generator (unix/clang, unix/gcc).
+    /// LAUNCHER:
+    ///     auto        ---> ccache or sccache, whichever is found on PATH first
+    ///     ccache      ---> ccache
+    ///     sccache     ---> sccache
+    ///     none        ---> Remove the launcher
+    #[clap(required = false, long, value_names = &["LAUNCHER"], verbatim_doc_comment)]
+    pub launcher: Option<String>,
+
+    /// Always run the CMake configure when generating.
+    /// By default configure is skipped if the configure command, toolchain file, compiler environment
+    /// and CMake sources did not change since the last successful configure.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub reconfigure: bool,
+
     /// Build CMake Project. Automatically uses CMAKE_BUILD_TYPE. Will not run without a build type set flag.
     #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
     pub build_project: bool,
@@ -106,11 +232,30 @@ pub struct BuildArgs {
     #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
     pub install_project: bool,
 
-    /// Clean CMake Project
+    /// Stage runtime assets next to the executables. Will not run without a build type set flag.
+    /// The asset directories are declared in the 'assets' list of the install config (cpm_install.json).
+    /// Files that were removed from an asset directory are removed from the build output as well.
+    /// STAGE_MODE:
+    /// (Falls back to the next mode if the selected one fails)
+    ///     symlink     ---> Symbolic links (default)
+    ///     hardlink    ---> Hard links
+    ///     copy        ---> Copies
+    #[clap(
+        required = false,
+        long,
+        short,
+        action = clap::ArgAction::Set,
+        value_names = &["STAGE_MODE"],
+        verbatim_doc_comment
+    )]
+    pub stage_assets: Option<Option<String>>,
+
+    /// Clean CMake Project. Use the 'clean' command for more options.
     /// WHAT_TO_CLEAN:
     /// (Combine characters to clean multiple things)
     ///     b   ---> Build directory
     ///     i   ---> Install directory
+    ///     p   ---> Dist (package) directory
     #[clap(
         required = false,
         long,
@@ -120,6 +265,49 @@ pub struct BuildArgs {
         verbatim_doc_comment
     )]
     pub clean_project: Option<Option<String>>,
+
+    /// Write the build summary (step durations, warnings and errors per file, first error) as JSON.
+    /// The summary is always printed after generating, building or installing.
+    /// FILE is relative to the project directory. Default: <build_dir>/cpm_build_report.json
+    #[clap(
+        required = false,
+        long,
+        action = clap::ArgAction::Set,
+        value_names = &["FILE"],
+        verbatim_doc_comment
+    )]
+    pub report: Option<Option<String>>,
+
+    /// Print the Ninja build profile (slowest outputs and targets, wall vs CPU time) after building.
+    /// Same as running 'stats build' after the build. Only available with the Ninja generator (unix/clang, unix/gcc).
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub profile: bool,
+
+    /// How to expose compile_commands.json in the project root after generating.
+    /// Only produced by the Ninja and Makefile generators (unix/clang, unix/gcc).
+    /// Modes:
+    ///     link    ---> Symlink to the build directory database (falls back to copy)
+    ///     copy    ---> Copy the build directory database
+    ///     none    ---> Do not export
+    #[clap(
+        required = false,
+        long,
+        default_value = "link",
+        value_names = &["MODE"],
+        verbatim_doc_comment
+    )]
+    pub compile_commands: String,
+
+    /// Additional build directories (comma separated) whose compile_commands.json are merged with the active one.
+    /// The merged database is always written as a file.
+    #[clap(
+        required = false,
+        long,
+        value_delimiter = ',',
+        value_names = &["BUILD_DIRS"],
+        verbatim_doc_comment
+    )]
+    pub merge_compile_commands: Vec<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -147,8 +335,548 @@ pub struct CacheArgs {
         verbatim_doc_comment
     )]
     pub edit_cache_key: Option<Vec<String>>,
+    /// Print the cached CMake configure command as structured options
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub configure_command: bool,
     /// Open the cache file in the default editor
     #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
     // No value needed
     pub open_cache: bool,
 }
+
+#[derive(Parser, Debug)]
+pub struct InfoArgs {
+    #[clap(subcommand)]
+    pub command: InfoCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum InfoCommands {
+    /// List the targets of the generated project (read from the CMake File API reply).
+    /// Requires the project to be generated with 'build -g' first.
+    #[clap(verbatim_doc_comment)]
+    Targets(InfoTargetsArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct InfoTargetsArgs {
+    /// Only show the target with this name.
+    #[clap(required = false, value_names = &["TARGET"], verbatim_doc_comment)]
+    pub target: Option<String>,
+    /// Configuration (build type) to show. Defaults to the cached build type.
+    #[clap(required = false, long, short, value_names = &["BUILD_TYPE"], verbatim_doc_comment)]
+    pub config: Option<String>,
+    /// Also list the source files of each target.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub sources: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct RunArgs {
+    /// Name of the executable target to run.
+    #[clap(required = true, value_names = &["TARGET"], verbatim_doc_comment)]
+    pub target: String,
+
+    /// Run the Debug build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub debug_build_type: bool,
+
+    /// Run the Release build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub release_build_type: bool,
+
+    /// Do not build the target before running it.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub no_build: bool,
+
+    /// Arguments passed to the target. Must be placed after '--'.
+    /// Example: cpm run app -- --verbose input.txt
+    #[clap(required = false, last = true, value_names = &["ARGS"], verbatim_doc_comment)]
+    pub args: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct TestArgs {
+    /// Test the Debug build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub debug_build_type: bool,
+
+    /// Test the Release build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub release_build_type: bool,
+
+    /// Only run tests whose names match the regular expression.
+    #[clap(required = false, long, short = 'R', value_names = &["REGEX"], verbatim_doc_comment)]
+    pub tests_regex: Option<String>,
+
+    /// Exclude tests whose names match the regular expression.
+    #[clap(required = false, long, short = 'E', value_names = &["REGEX"], verbatim_doc_comment)]
+    pub exclude_regex: Option<String>,
+
+    /// Only run tests whose labels match the regular expression.
+    #[clap(required = false, long, short = 'L', value_names = &["REGEX"], verbatim_doc_comment)]
+    pub label_regex: Option<String>,
+
+    /// Exclude tests whose labels match the regular expression.
+    #[clap(required = false, long, value_names = &["REGEX"], verbatim_doc_comment)]
+    pub label_exclude: Option<String>,
+
+    /// Run tests in parallel using the given number of jobs.
+    #[clap(required = false, long, short, value_names = &["JOBS"], verbatim_doc_comment)]
+    pub jobs: Option<u32>,
+
+    /// Only run the tests that failed previously.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub rerun_failed: bool,
+
+    /// Default time limit for each test in seconds.
+    #[clap(required = false, long, short, value_names = &["SECONDS"], verbatim_doc_comment)]
+    pub timeout: Option<u32>,
+
+    /// Write a JUnit XML report.
+    /// If no file is provided the report is written to '<build_dir>/junit-<build_type>.xml'.
+    #[clap(
+        required = false,
+        long,
+        action = clap::ArgAction::Set,
+        value_names = &["FILE"],
+        verbatim_doc_comment
+    )]
+    pub junit: Option<Option<String>>,
+
+    /// Build the project before running the tests.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub build_project: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct PackageArgs {
+    /// Package the Debug build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub debug_build_type: bool,
+
+    /// Package the Release build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub release_build_type: bool,
+
+    /// CPack generators to use (comma separated). Defaults to ZIP on Windows and TGZ otherwise.
+    /// Supported generators:
+    ///     ZIP     ---> All platforms
+    ///     TGZ     ---> All platforms
+    ///     DEB     ---> Linux, requires dpkg
+    ///     NSIS    ---> Windows, requires makensis
+    #[clap(
+        required = false,
+        long,
+        short = 'G',
+        value_delimiter = ',',
+        value_names = &["GENERATORS"],
+        verbatim_doc_comment
+    )]
+    pub generators: Vec<String>,
+
+    /// Build the project before packaging.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub build_project: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CleanArgs {
+    /// What to clean. Defaults to 'bi'.
+    /// WHAT_TO_CLEAN:
+    /// (Combine characters to clean multiple things)
+    ///     b   ---> Build directory
+    ///     i   ---> Install directory
+    ///     p   ---> Dist (package) directory
+    #[clap(required = false, value_names = &["WHAT_TO_CLEAN"], verbatim_doc_comment)]
+    pub what_to_clean: Option<String>,
+
+    /// Only clean the Debug build type.
+    /// Install/Dist: removes '<dir>/<os_release>/Debug'. Build: runs CMake's 'clean' target for Debug.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub debug_build_type: bool,
+
+    /// Only clean the Release build type.
+    /// Install/Dist: removes '<dir>/<os_release>/Release'. Build: runs CMake's 'clean' target for Release.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub release_build_type: bool,
+
+    /// Only clean the build directory if it was generated for this system type.
+    /// System types:
+    ///     nt/msvc     ---> Windows, MSVC compiler
+    ///     unix/clang  ---> Unix, Clang compiler
+    ///     unix/gcc    ---> Unix, GCC compiler
+    #[clap(required = false, long, short, value_names = &["SYSTEM_TYPE"], verbatim_doc_comment)]
+    pub system_type: Option<String>,
+
+    /// Run CMake's 'clean' target instead of deleting the build directory. Keeps the CMake cache.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub soft: bool,
+
+    /// Only list what would be deleted.
+    #[clap(required = false, long, short = 'n', action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub dry_run: bool,
+}
+
+impl CleanArgs {
+    // Used by 'build -c' which only selects the directories.
+    pub fn new(what_to_clean: &str) -> Self {
+        CleanArgs {
+            what_to_clean: Some(what_to_clean.to_string()),
+            debug_build_type: false,
+            release_build_type: false,
+            system_type: None,
+            soft: false,
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CMakeCacheArgs {
+    /// Only list entries whose key contains the filter (case insensitive).
+    #[clap(required = false, value_names = &["FILTER"], verbatim_doc_comment)]
+    pub filter: Option<String>,
+
+    /// Print the value of a cache entry.
+    #[clap(required = false, long, short, value_names = &["KEY"], verbatim_doc_comment)]
+    pub get: Option<String>,
+
+    /// Set a cache entry and re-run the CMake configure. Format: KEY=VALUE or KEY:TYPE=VALUE.
+    /// The type of an existing entry is kept if no type is provided.
+    #[clap(required = false, long, short, value_names = &["KEY=VALUE"], verbatim_doc_comment)]
+    pub set: Option<String>,
+
+    /// Include advanced entries when listing.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub advanced: bool,
+
+    /// Include INTERNAL and STATIC entries when listing.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub internal: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct StatsArgs {
+    #[clap(subcommand)]
+    pub command: StatsCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum StatsCommands {
+    /// Analyze the Ninja build log (<build_dir>/.ninja_log) of the last build.
+    /// Shows the slowest outputs and targets, wall vs CPU time and the change versus the previous build.
+    /// Only available with the Ninja generator (unix/clang, unix/gcc).
+    #[clap(verbatim_doc_comment)]
+    Build(StatsBuildArgs),
+    /// Show the hit/miss statistics of the compiler launcher (ccache or sccache).
+    /// Uses the launcher the project was generated with, otherwise the 'compiler_launcher' setting.
+    #[clap(verbatim_doc_comment)]
+    Cache(StatsCacheArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct StatsBuildArgs {
+    /// Number of outputs and targets to show.
+    #[clap(
+        required = false,
+        long,
+        short = 'n',
+        default_value_t = 10,
+        value_names = &["COUNT"],
+        verbatim_doc_comment
+    )]
+    pub top: usize,
+}
+
+#[derive(Parser, Debug)]
+pub struct StatsCacheArgs {
+    /// Reset the statistics after showing them.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub zero: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CoverageArgs {
+    /// Use the Debug build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub debug_build_type: bool,
+
+    /// Use the Release build. Defaults to the cached build type.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub release_build_type: bool,
+
+    /// Build the project before running the tests.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub build_project: bool,
+
+    /// Only run tests whose names match the regular expression.
+    #[clap(required = false, long, short = 'R', value_names = &["REGEX"], verbatim_doc_comment)]
+    pub tests_regex: Option<String>,
+
+    /// Do not run the tests, only collect the coverage data of previous runs.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub no_test: bool,
+
+    /// Directory for the lcov tracefile and the HTML report, relative to the project directory.
+    /// Default: <build_dir>/coverage
+    #[clap(required = false, long, short, value_names = &["DIR"], verbatim_doc_comment)]
+    pub output_dir: Option<String>,
+
+    /// Do not generate the HTML report.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub no_html: bool,
+
+    /// Fail if the total line coverage (percent) is below the minimum.
+    #[clap(required = false, long, short, value_names = &["PERCENT"], verbatim_doc_comment)]
+    pub min: Option<f64>,
+}
+
+#[derive(Parser, Debug)]
+pub struct NewArgs {
+    /// Name of the project. Used for the CMake project, the main target and the C++ namespace.
+    #[clap(required = true, value_names = &["NAME"], verbatim_doc_comment)]
+    pub name: String,
+
+    /// Template to create the project from. See 'templates list' for the project and user templates.
+    /// Embedded templates:
+    ///     executable      ---> Executable with a hello world main (short: exe)
+    ///     static-library  ---> Static library with a public include directory (short: static)
+    ///     shared-library  ---> Shared library with a public include directory (short: shared)
+    ///     header-only     ---> Header only (INTERFACE) library (short: header)
+    ///     library-tests   ---> Static library with CTest tests (short: lib-tests)
+    #[clap(
+        required = false,
+        long,
+        short,
+        default_value = "executable",
+        value_names = &["TEMPLATE"],
+        verbatim_doc_comment
+    )]
+    pub template: String,
+
+    /// C++ standard of the project.
+    #[clap(
+        required = false,
+        long = "std",
+        short = 's',
+        default_value = "17",
+        value_names = &["STANDARD"],
+        verbatim_doc_comment
+    )]
+    pub cxx_standard: String,
+
+    /// Directory to create the project in, relative to the current directory. Default: ./<NAME>
+    /// Use '.' to create the project in the current directory.
+    #[clap(required = false, long, short, value_names = &["DIR"], verbatim_doc_comment)]
+    pub path: Option<String>,
+
+    /// Set a template variable. Can be repeated. Lists are comma separated.
+    /// Embedded template variables:
+    ///     version              ---> Project version (default: 0.1.0)
+    ///     project_description  ---> CMake project description
+    ///     packages             ---> Packages to find with find_package, i.e. -D packages=fmt,spdlog
+    #[clap(
+        required = false,
+        long = "define",
+        short = 'D',
+        value_names = &["KEY=VALUE"],
+        verbatim_doc_comment
+    )]
+    pub defines: Vec<String>,
+
+    /// Overwrite existing files.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplatesArgs {
+    #[clap(subcommand)]
+    pub command: TemplatesCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum TemplatesCommands {
+    /// List the templates available to 'new', with their source and description.
+    /// Project templates (.cpm/templates) override user templates ('templates_dir' setting, default: <exe_dir>/templates),
+    /// which override the embedded templates with the same name.
+    #[clap(verbatim_doc_comment)]
+    List(TemplatesListArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplatesListArgs {
+    /// Also show the variables of each template with their default values.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub variables: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct AddArgs {
+    #[clap(subcommand)]
+    pub command: AddCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum AddCommands {
+    /// Add an executable target in its own subdirectory (template: add-executable).
+    #[clap(verbatim_doc_comment)]
+    Executable(AddTargetArgs),
+    /// Add a library target in its own subdirectory (template: add-library).
+    /// Static by default, use '-D shared=ON' for a shared library.
+    #[clap(verbatim_doc_comment)]
+    Library(AddTargetArgs),
+    /// Add a CTest test executable in its own subdirectory (template: add-test).
+    /// The project needs to call enable_testing() or include(CTest).
+    #[clap(verbatim_doc_comment)]
+    Test(AddTargetArgs),
+    /// Add a vcpkg package to the install config (config.windows.packages) and show how to use it from CMake.
+    /// The find_package and target_link_libraries calls come from the usage file of the installed port.
+    #[clap(verbatim_doc_comment)]
+    Package(AddPackageArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct AddTargetArgs {
+    /// Name of the target. Also the default subdirectory.
+    #[clap(required = true, value_names = &["NAME"], verbatim_doc_comment)]
+    pub name: String,
+
+    /// Subdirectory of the target, relative to the project root.
+    /// Default: <NAME> for executables and libraries, tests/<NAME> for tests.
+    #[clap(required = false, long, short, value_names = &["DIR"], verbatim_doc_comment)]
+    pub dir: Option<String>,
+
+    /// Targets to link the new target with, i.e. -l core,fmt::fmt
+    #[clap(
+        required = false,
+        long,
+        short,
+        value_delimiter = ',',
+        value_names = &["TARGETS"],
+        verbatim_doc_comment
+    )]
+    pub link: Vec<String>,
+
+    /// Set a template variable. Can be repeated. Lists are comma separated.
+    #[clap(
+        required = false,
+        long = "define",
+        short = 'D',
+        value_names = &["KEY=VALUE"],
+        verbatim_doc_comment
+    )]
+    pub defines: Vec<String>,
+
+    /// Overwrite existing files.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct AddPackageArgs {
+    /// Name of the vcpkg port.
+    #[clap(required = true, value_names = &["PORT"], verbatim_doc_comment)]
+    pub name: String,
+
+    /// Triplet to install the package for. Default: the triplet of the listed packages, otherwise x64-windows.
+    #[clap(required = false, long, short, value_names = &["TRIPLET"], verbatim_doc_comment)]
+    pub triplet: Option<String>,
+
+    /// Install the package with vcpkg right away.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub install: bool,
+
+    /// Target to link the package with, used in the printed snippet and by --insert.
+    #[clap(required = false, long, short = 'T', value_names = &["TARGET"], verbatim_doc_comment)]
+    pub target: Option<String>,
+
+    /// Insert the find_package and target_link_libraries calls next to the definition of the target.
+    /// Calls that are already there are not added again.
+    #[clap(
+        required = false,
+        long,
+        requires = "target",
+        action = clap::ArgAction::SetTrue,
+        verbatim_doc_comment
+    )]
+    pub insert: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct RemoveArgs {
+    #[clap(subcommand)]
+    pub command: RemoveCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum RemoveCommands {
+    /// Remove a vcpkg package from the install config (config.windows.packages).
+    /// Lists the find_package and target_link_libraries calls that still use it. The port stays installed.
+    #[clap(verbatim_doc_comment)]
+    Package(RemovePackageArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct RemovePackageArgs {
+    /// Name of the vcpkg port.
+    #[clap(required = true, value_names = &["PORT"], verbatim_doc_comment)]
+    pub name: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct DeinitArgs {
+    /// Keep cpm_install.json, it lists the packages of the project.
+    #[clap(required = false, long, short, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub keep_install_config: bool,
+
+    /// Only list what would be removed.
+    #[clap(required = false, long, short = 'n', action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct IdeArgs {
+    #[clap(subcommand)]
+    pub command: IdeCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum IdeCommands {
+    /// Write the VS Code workspace files (.vscode/): tasks.json, launch.json, c_cpp_properties.json and
+    /// the clangd arguments in settings.json. Entries named 'cpm: ...' (the 'CPM' C/C++ configuration)
+    /// are regenerated, other entries are kept.
+    #[clap(verbatim_doc_comment)]
+    Vscode(IdeVscodeArgs),
+    /// Write the Visual Studio (Open Folder) files: CMakeSettings.json with a 'cpm-<compiler>-<build type>'
+    /// configuration per system type and build type, and launch.vs.json for the executable targets.
+    /// Entries named 'cpm-...' are regenerated, other entries are kept.
+    #[clap(verbatim_doc_comment)]
+    Vs(IdeVsArgs),
+    /// Write the CLion CMake profiles (.idea/cmake.xml), a 'cpm-<compiler>-<build type>' profile per
+    /// system type and build type. Profiles named 'cpm-...' are regenerated, other profiles are kept.
+    #[clap(verbatim_doc_comment)]
+    Clion(IdeClionArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct IdeVscodeArgs {
+    /// Overwrite the files that can not be parsed or have comments, instead of skipping them.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct IdeVsArgs {
+    /// Overwrite the files that can not be parsed or have comments, instead of skipping them.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct IdeClionArgs {
+    /// Overwrite .idea/cmake.xml if it has no CMake profiles list, instead of skipping it.
+    #[clap(required = false, long, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
+    pub force: bool,
+}
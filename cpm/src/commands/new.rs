@@ -0,0 +1,100 @@
+use spdlog::prelude::*;
+use std::path::{ Path, PathBuf };
+
+use crate::commands::NewArgs;
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::templates::{ self, Variables };
+
+const CXX_STANDARDS: [&str; 6] = ["11", "14", "17", "20", "23", "26"];
+
+pub fn run(args: NewArgs) {
+    debug!("Running the New command with arguments: {:#?}", args);
+
+    // The name ends up in CMake target names, paths and C++ namespaces
+    let valid_name =
+        !args.name.is_empty() &&
+        args.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        RuntimeErrors::InvalidProjectName(args.name.clone()).exit();
+    }
+
+    // Only needed to find the project and user templates
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    let template = match templates::find(&settings, &args.template) {
+        Some(template) => template,
+        None => {
+            let available: Vec<String> = templates
+                ::project_templates(&settings)
+                .into_iter()
+                .map(|template| template.name)
+                .collect();
+            RuntimeErrors::TemplateNotFound(args.template.clone(), available.join(", ")).exit();
+            return;
+        }
+    };
+
+    if !CXX_STANDARDS.contains(&args.cxx_standard.as_str()) {
+        RuntimeErrors::InvalidCxxStandard(args.cxx_standard.clone()).exit();
+    }
+
+    let defines: Variables = match templates::parse_defines(&args.defines) {
+        Ok(defines) => defines,
+        Err(define) => {
+            RuntimeErrors::InvalidTemplateVariable(define).exit();
+            return;
+        }
+    };
+
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    let destination: PathBuf = match &args.path {
+        Some(path) => current_dir.join(path),
+        None => current_dir.join(&args.name),
+    };
+
+    info!(
+        "Creating '{}' from the {} '{}' template (C++{}) in: {}",
+        args.name,
+        template.source.name(),
+        template.name,
+        args.cxx_standard,
+        destination.display()
+    );
+
+    let variables = templates::variables(&template, &args.name, &args.cxx_standard, &defines);
+    match templates::render(&template, &variables, &destination, args.force) {
+        Ok((written, skipped)) => {
+            for path in &written {
+                info!("Created: {}", relative(&destination, path));
+            }
+            for path in &skipped {
+                warn!(
+                    "Skipped existing file (use --force to overwrite): {}",
+                    relative(&destination, path)
+                );
+            }
+        }
+        Err(e) => RuntimeErrors::ScaffoldFailed(e.to_string()).exit(),
+    }
+
+    info!("Project created. Run 'cpm init' in '{}' to start using it.", destination.display());
+}
+
+fn relative(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base).unwrap_or(path).display().to_string()
+}
@@ -0,0 +1,166 @@
+use spdlog::prelude::*;
+use std::fs::File;
+use std::io::{ self, Write };
+use std::path::{ Path, PathBuf };
+
+use crate::commands::PackageArgs;
+use crate::commands::build::{ build_cmake_project, resolve_build_type };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::cmd;
+use crate::internal::fingerprint;
+
+pub fn run(args: PackageArgs) {
+    debug!("Running the Package command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    let build_type = resolve_build_type(args.debug_build_type, args.release_build_type, &settings);
+    info!("Build Type: {}", build_type);
+
+    let generators = select_generators(&settings, &args.generators);
+    info!("CPack generators: {}", generators.join(", "));
+
+    // CPackConfig.cmake is only written by CMake when the project includes CPack.
+    let cpack_config = Path::new(&settings.build_dir).join("CPackConfig.cmake");
+    if !cpack_config.exists() {
+        RuntimeErrors::CPackConfigNotFound(cpack_config.display().to_string()).exit();
+    }
+
+    if args.build_project {
+        // A failed build would package the artifacts of the previous build
+        let output = build_cmake_project(&settings, &build_type);
+        if output.status != 0 {
+            RuntimeErrors::BuildStepFailed("build".to_string(), output.status).exit();
+        }
+    }
+
+    // i.e. <dist_dir>/<os_release>/<build_type>, mirroring the install layout
+    let output_dir = dist_dir(&settings).join(&settings.os_release).join(&build_type);
+    if let Err(e) = std::fs::create_dir_all(&output_dir) {
+        RuntimeErrors::PackageFailed(Some(e.to_string())).exit();
+    }
+
+    let output = cmd::execute_and_capture_output(
+        vec![
+            "cpack".to_string(),
+            "--config".to_string(),
+            cpack_config.to_str().unwrap().to_string(),
+            "-G".to_string(),
+            generators.join(";"),
+            "-C".to_string(),
+            build_type.clone(),
+            "-B".to_string(),
+            output_dir.to_str().unwrap().to_string()
+        ]
+    );
+    info!("{}", output.stdout);
+    if output.status != 0 {
+        RuntimeErrors::PackageFailed(
+            Some(format!("CPack exited with code {}: {}", output.status, output.stderr))
+        ).exit();
+    }
+
+    let packages = generated_packages(&output.stdout);
+    if packages.is_empty() {
+        RuntimeErrors::PackageFailed(
+            Some("CPack did not report any generated package".to_string())
+        ).exit();
+    }
+
+    match write_checksums(&output_dir, &packages) {
+        Ok(manifest) => info!("Checksums written to: {}", manifest.display()),
+        Err(e) => RuntimeErrors::PackageFailed(Some(e.to_string())).exit(),
+    }
+}
+
+pub fn dist_dir(settings: &Settings) -> PathBuf {
+    // Settings created before the 'Dist' directory existed do not have it cached
+    if settings.dist_dir.is_empty() {
+        Path::new(&settings.working_dir).join("Dist")
+    } else {
+        PathBuf::from(&settings.dist_dir)
+    }
+}
+
+fn select_generators(settings: &Settings, requested: &[String]) -> Vec<String> {
+    if requested.is_empty() {
+        return match settings.os.as_str() {
+            "windows" => vec!["ZIP".to_string()],
+            _ => vec!["TGZ".to_string()],
+        };
+    }
+
+    let mut generators = Vec::new();
+    for generator in requested {
+        let generator = generator.trim().to_uppercase();
+        let supported = match generator.as_str() {
+            "ZIP" | "TGZ" => true,
+            "DEB" => settings.os == "linux" && tool_exists("dpkg"),
+            "NSIS" => settings.os == "windows" && tool_exists("makensis"),
+            _ => false,
+        };
+        if !supported {
+            RuntimeErrors::PackageGeneratorNotSupported(generator.clone()).exit();
+        }
+        if !generators.contains(&generator) {
+            generators.push(generator);
+        }
+    }
+    generators
+}
+
+fn tool_exists(tool: &str) -> bool {
+    cmd::find_program(tool).is_some()
+}
+
+// CPack reports every package it writes:
+//     CPack: - package: C:/project/Dist/Windows/Release/sample-0.1.1-win64.zip generated.
+fn generated_packages(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once("CPack: - package:"))
+        .map(|(_, rest)| rest.trim().trim_end_matches("generated.").trim())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+// Writes a 'SHA256SUMS' file next to the packages, in the format 'sha256sum -c' understands.
+fn write_checksums(output_dir: &Path, packages: &[PathBuf]) -> io::Result<PathBuf> {
+    let mut contents = String::new();
+    for package in packages {
+        let checksum = fingerprint::hash_file(package)?;
+        let file_name = package
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        info!("Package: {} (sha256: {})", file_name, checksum);
+        contents.push_str(&format!("{}  {}\n", checksum, file_name));
+    }
+
+    let manifest = output_dir.join("SHA256SUMS");
+    let mut file = File::create(&manifest)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(manifest)
+}
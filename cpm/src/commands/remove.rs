@@ -0,0 +1,128 @@
+use spdlog::prelude::*;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use crate::commands::add::project_listfiles;
+use crate::commands::{ RemoveArgs, RemoveCommands, RemovePackageArgs };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::cmake_lists;
+use crate::internal::install;
+use crate::internal::package_usage;
+use crate::internal::settings::Settings;
+
+pub fn run(args: RemoveArgs) {
+    debug!("Running the Remove command with arguments: {:#?}", args);
+
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    match args.command {
+        RemoveCommands::Package(package_args) => remove_package(&settings, package_args),
+    }
+}
+
+fn remove_package(settings: &Settings, args: RemovePackageArgs) {
+    let config_path = Path::new(&settings.install_json_path);
+    let contents = match fs::read_to_string(config_path) {
+        Ok(contents) => contents,
+        Err(_) => {
+            RuntimeErrors::JSONFileNotFound(Some(settings.install_json_path.clone())).exit();
+            return;
+        }
+    };
+
+    // The triplet is needed to find the usage of the installed port
+    let triplet = install
+        ::retrieve_install(config_path)
+        .ok()
+        .and_then(|config| config.config.windows)
+        .and_then(|windows| {
+            windows.packages
+                .into_iter()
+                .find(|package| package.library.eq_ignore_ascii_case(&args.name))
+                .map(|package| package.triplet)
+        })
+        .unwrap_or_default();
+
+    match install::remove_package(&contents, &args.name) {
+        Ok(Some(edited)) => {
+            if let Err(e) = fs::write(config_path, edited) {
+                let path = settings.install_json_path.clone();
+                RuntimeErrors::InstallConfigEditFailed(path, e.to_string()).exit();
+            }
+            info!("Removed package '{}' from: {}", args.name, config_path.display());
+        }
+        Ok(None) => RuntimeErrors::PackageNotInConfig(args.name.clone()).exit(),
+        Err(reason) => {
+            let path = settings.install_json_path.clone();
+            RuntimeErrors::InstallConfigEditFailed(path, reason).exit();
+        }
+    }
+
+    // The package name of find_package may differ from the port name (i.e. gtest -> GTest)
+    let usage = if settings.toolchain_path.is_empty() || triplet.is_empty() {
+        None
+    } else {
+        package_usage::load(&settings.toolchain_path, &triplet, &args.name)
+    };
+    let mut packages: Vec<String> = vec![args.name.clone()];
+    let mut link_targets: Vec<String> = Vec::new();
+    if let Some(usage) = usage {
+        packages.extend(usage.package());
+        link_targets = usage.link_targets;
+    }
+
+    let project_root = PathBuf::from(&settings.working_dir);
+    for path in project_listfiles(settings) {
+        let Ok((contents, commands)) = cmake_lists::load(&path) else {
+            continue;
+        };
+        for command in &commands {
+            let arguments = command.argument_list();
+            let uses_package = match command.name.as_str() {
+                "find_package" => {
+                    let name = arguments.first().cloned().unwrap_or_default();
+                    packages.iter().any(|package| package.eq_ignore_ascii_case(&name))
+                }
+                "target_link_libraries" => {
+                    arguments.iter().skip(1).any(|argument| link_targets.contains(argument))
+                }
+                _ => false,
+            };
+            if uses_package {
+                warn!(
+                    "Still used by {}:{}: {}({})",
+                    path.strip_prefix(&project_root).unwrap_or(&path).display(),
+                    cmake_lists::line_number(&contents, command.start),
+                    command.name,
+                    command.arguments.split_whitespace().collect::<Vec<&str>>().join(" ")
+                );
+            }
+        }
+    }
+
+    if !triplet.is_empty() {
+        info!(
+            "The port stays installed, run 'vcpkg remove {}:{}' to uninstall it.",
+            args.name,
+            triplet
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use spdlog::prelude::*;
+use std::collections::HashSet;
+use std::path::{ Path, PathBuf };
+
+use crate::commands::RunArgs;
+use crate::commands::build::{ build_cmake_target, resolve_build_type, stage_runtime_assets };
+use crate::commands::info::load_codemodel;
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::file_api::CodeModel;
+use crate::internal::install::retrieve_install;
+use crate::internal::stage::LinkMode;
+use crate::internal::cmd;
+
+pub fn run(args: RunArgs) {
+    debug!("Running the Run command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    let build_type = resolve_build_type(args.debug_build_type, args.release_build_type, &settings);
+    info!("Build Type: {}", build_type);
+
+    // Resolve the target before building so a typo does not trigger a full build.
+    let codemodel = load_codemodel(&settings);
+    let configuration = match codemodel.configuration(&build_type) {
+        Some(configuration) => configuration,
+        None => {
+            RuntimeErrors::ConfigurationNotFound(build_type).exit();
+            return;
+        }
+    };
+    let target = match configuration.target(&args.target) {
+        Some(target) => target,
+        None => {
+            RuntimeErrors::TargetNotFound(args.target.clone()).exit();
+            return;
+        }
+    };
+    if !target.is_executable() {
+        RuntimeErrors::TargetNotExecutable(target.name.clone()).exit();
+    }
+
+    if !args.no_build {
+        // Do not launch the binary of the previous build
+        let output = build_cmake_target(&settings, &build_type, &target.name);
+        if output.status != 0 {
+            RuntimeErrors::BuildStepFailed("build".to_string(), output.status).exit();
+        }
+        stage_runtime_assets(&settings, &build_type, LinkMode::Symlink);
+    }
+
+    let artifact = match target.artifacts.first() {
+        Some(artifact) => codemodel.artifact_path(artifact),
+        None => {
+            RuntimeErrors::ArtifactNotFound(target.name.clone()).exit();
+            return;
+        }
+    };
+    if !artifact.exists() {
+        RuntimeErrors::ArtifactNotFound(artifact.display().to_string()).exit();
+    }
+
+    let library_dirs = runtime_library_dirs(&settings, &codemodel, &build_type);
+    let envs = runtime_environment(&settings, &library_dirs);
+
+    let mut cmd_array = vec![artifact.to_str().unwrap().to_string()];
+    cmd_array.extend(args.args.iter().cloned());
+
+    info!("Running: {}", cmd_array.join(" "));
+    let exit_code = cmd::execute_interactive(cmd_array, envs);
+
+    if exit_code != 0 {
+        warn!("'{}' exited with code {}", target.name, exit_code);
+    }
+    std::process::exit(exit_code);
+}
+
+// Directories that contain shared libraries the executable might need at runtime:
+// - directories of the project's own shared library targets
+// - vcpkg's installed bin directories for the triplets in the install config
+pub fn runtime_library_dirs(
+    settings: &Settings,
+    codemodel: &CodeModel,
+    build_type: &str
+) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+
+    if let Some(configuration) = codemodel.configuration(build_type) {
+        for target in configuration.targets.iter().filter(|t| t.is_shared_library()) {
+            for artifact in &target.artifacts {
+                if let Some(parent) = codemodel.artifact_path(artifact).parent() {
+                    dirs.push(parent.to_path_buf());
+                }
+            }
+        }
+    }
+
+    if settings.using_toolchain && !settings.toolchain_path.is_empty() {
+        for triplet in vcpkg_triplets(settings) {
+            // vcpkg places debug binaries under 'installed/<triplet>/debug/bin'
+            let installed = Path::new(&settings.toolchain_path).join("installed").join(&triplet);
+            let manifest_installed = Path::new(&settings.build_dir)
+                .join("vcpkg_installed")
+                .join(&triplet);
+            for root in [installed, manifest_installed] {
+                if build_type == "Debug" {
+                    dirs.push(root.join("debug").join("bin"));
+                }
+                dirs.push(root.join("bin"));
+            }
+        }
+    }
+
+    // Keep the order but drop duplicates and directories that do not exist
+    let mut seen = HashSet::new();
+    dirs.retain(|dir| dir.is_dir() && seen.insert(dir.clone()));
+    dirs
+}
+
+fn vcpkg_triplets(settings: &Settings) -> Vec<String> {
+    let mut triplets: Vec<String> = Vec::new();
+
+    match retrieve_install(Path::new(&settings.install_json_path)) {
+        Ok(config) => {
+            if let Some(windows_config) = &config.config.windows {
+                for package in &windows_config.packages {
+                    if !triplets.contains(&package.triplet) {
+                        triplets.push(package.triplet.clone());
+                    }
+                }
+            }
+        }
+        Err(e) => warn!("Failed to read the install config, skipping vcpkg runtime paths: {}", e),
+    }
+
+    triplets
+}
+
+// Prepends the library directories to the OS specific runtime library search path.
+pub fn runtime_environment(
+    settings: &Settings,
+    library_dirs: &[PathBuf]
+) -> Vec<(String, String)> {
+    if library_dirs.is_empty() {
+        return vec![];
+    }
+
+    let variable = runtime_library_variable(settings);
+
+    let mut paths: Vec<PathBuf> = library_dirs.to_vec();
+    if let Some(existing) = std::env::var_os(variable) {
+        paths.extend(std::env::split_paths(&existing));
+    }
+
+    match std::env::join_paths(paths) {
+        Ok(joined) => {
+            debug!("{}={}", variable, joined.to_string_lossy());
+            vec![(variable.to_string(), joined.to_string_lossy().to_string())]
+        }
+        Err(e) => {
+            warn!("Failed to set up the runtime library path: {}", e);
+            vec![]
+        }
+    }
+}
+
+// Environment variable the OS searches for shared libraries at runtime.
+pub fn runtime_library_variable(settings: &Settings) -> &'static str {
+    match settings.os.as_str() {
+        "windows" => "PATH",
+        "macos" => "DYLD_LIBRARY_PATH",
+        _ => "LD_LIBRARY_PATH",
+    }
+}
@@ -1,13 +1,11 @@
 use spdlog::prelude::*;
-use std::fs::File;
-use std::io::Read;
 use std::path::Path;
 
 use crate::commands::SetupArgs;
 use crate::errors::errors::RuntimeErrors;
 use crate::internal::settings::Settings;
 use crate::internal::cmd;
-use crate::internal::install::Config;
+use crate::internal::install::{ retrieve_install, Config };
 
 pub fn run(args: SetupArgs) {
     debug!("Running the Initialization command with arguments: {:#?}", args);
@@ -76,7 +74,7 @@ pub fn run(args: SetupArgs) {
     if let Some(toolchain_path) = &args.toolchain {
         debug!("Path before trim: {}", toolchain_path);
         // If the provided path has any '/' or '\' characters at the very end, remove them
-        let toolchain_path = toolchain_path.trim_end_matches(|c| (c == '/' || c == '\\'));
+        let toolchain_path = toolchain_path.trim_end_matches(['/', '\\']);
         debug!("Path after trim: {}", toolchain_path);
         settings.toolchain_path = toolchain_path.to_string();
         check_toolchain(&mut settings);
@@ -90,31 +88,15 @@ pub fn run(args: SetupArgs) {
     }
     // Auto detect toolchain and run setup otherwise manually set up toolchain.
     if args.no_toolchain_path {
+        RuntimeErrors::NotImplemented.exit();
         return;
     }
     // Use provided path and try to run setup.
-    if let Some(toolchain_path) = &args.use_toolchain_path {
-        return;
+    if args.use_toolchain_path.is_some() {
+        RuntimeErrors::NotImplemented.exit();
     }
 }
 
-fn retrieve_install(file_path: &Path) -> Result<Config, Box<dyn std::error::Error>> {
-    // Open the file
-    let mut file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
-
-    // Read the contents into a string
-    let mut config_data = String::new();
-    file.read_to_string(&mut config_data).map_err(|e| format!("Failed to read file data: {}", e))?;
-
-    // Deserialize the JSON string into Config struct
-    let config: Config = serde_json
-        ::from_str(&config_data)
-        .map_err(|e| format!("Failed to parse JSON data: {}", e))?;
-
-    // Return the deserialized config
-    Ok(config)
-}
-
 fn check_toolchain(settings: &mut Settings) {
     // Run through a match of know toolchains and find their appropriate .cmake file.
     // Current list of know toolchains:
@@ -416,7 +398,12 @@ fn windows_post_install(settings: &Settings, config: &Config) {
                 }
                 _ => {
                     // No exit here as it's not a critical error.
-                    RuntimeErrors::PostInstallNoDefinition(Some(post_install.to_string()));
+                    warn!(
+                        "{}",
+                        RuntimeErrors::PostInstallNoDefinition(
+                            Some(post_install.to_string())
+                        ).error_message()
+                    );
                 }
             }
         }
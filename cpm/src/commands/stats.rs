@@ -0,0 +1,188 @@
+use spdlog::prelude::*;
+use std::io;
+use std::path::Path;
+
+use crate::commands::{ StatsArgs, StatsCacheArgs, StatsCommands };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::cmd;
+use crate::internal::launcher::{ self, Launcher };
+use crate::internal::ninja_log::{ self, BuildProfile };
+
+pub fn run(args: StatsArgs) {
+    debug!("Running the Stats command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let mut settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    match args.command {
+        StatsCommands::Build(build_args) => {
+            if print_build_profile(&mut settings, build_args.top).is_err() {
+                let path = ninja_log::log_path(Path::new(&settings.build_dir));
+                RuntimeErrors::NinjaLogNotFound(path.display().to_string()).exit();
+            }
+        }
+        StatsCommands::Cache(cache_args) => {
+            print_launcher_stats(&settings, &cache_args);
+        }
+    }
+}
+
+fn print_launcher_stats(settings: &Settings, args: &StatsCacheArgs) {
+    // Prefer what the project was actually generated with
+    let found = match launcher::configured(&settings.last_cmake_configuration_command) {
+        Some(configured) => Some(configured),
+        None =>
+            match settings.compiler_launcher.as_str() {
+                "" | "none" => None,
+                "auto" => launcher::detect(),
+                name =>
+                    match Launcher::parse(name) {
+                        Some(selected) => selected.find().map(|path| (selected, path)),
+                        None => {
+                            RuntimeErrors::InvalidLauncher(name.to_string()).exit();
+                            None
+                        }
+                    }
+            }
+    };
+
+    let (selected, path) = match found {
+        Some(found) => found,
+        None => {
+            warn!(
+                "No compiler launcher configured. Generate with 'build -g --launcher auto' or set 'compiler_launcher' in the cache."
+            );
+            return;
+        }
+    };
+
+    let output = cmd::execute_and_capture_output(launcher::stats_command(&path));
+    if output.status != 0 {
+        RuntimeErrors::LauncherNotFound(selected.program().to_string()).exit();
+    }
+    info!("{} statistics:\n{}", selected.program(), output.stdout);
+
+    if args.zero {
+        cmd::execute_and_capture_output(launcher::zero_stats_command(&path));
+        info!("{} statistics reset.", selected.program());
+    }
+}
+
+// Prints the profile of the last ninja run and how it compares to the run before it.
+// Fails if the build directory has no ninja log (not built yet or not a Ninja generator).
+pub fn print_build_profile(settings: &mut Settings, top: usize) -> io::Result<()> {
+    let (entries, log_size) = ninja_log::load(Path::new(&settings.build_dir))?;
+    let run = ninja_log::last_run(&entries);
+    if run.is_empty() {
+        warn!("The ninja log has no entries. Build the project first.");
+        return Ok(());
+    }
+
+    let current = ninja_log::profile(&run, log_size, top);
+
+    // Record the run once, rerunning the command must not compare the run against itself
+    let previous = match &settings.last_build_profile {
+        Some(last) if last.log_size == log_size => settings.previous_build_profile.clone(),
+        _ => {
+            settings.previous_build_profile = settings.last_build_profile.take();
+            settings.last_build_profile = Some(current.clone());
+            let _ = settings.save_default();
+            settings.previous_build_profile.clone()
+        }
+    };
+
+    print_profile(&current, previous.as_ref(), top);
+    Ok(())
+}
+
+fn print_profile(current: &BuildProfile, previous: Option<&BuildProfile>, top: usize) {
+    let parallelism = if current.wall_ms > 0 {
+        (current.cpu_ms as f64) / (current.wall_ms as f64)
+    } else {
+        0.0
+    };
+
+    let mut output = format!("Ninja build profile ({} edges):", current.edges);
+    output.push_str(
+        &format!(
+            "\n  Wall time: {}{}",
+            seconds(current.wall_ms),
+            delta(current.wall_ms, previous.map(|p| p.wall_ms))
+        )
+    );
+    output.push_str(
+        &format!(
+            "\n  CPU time:  {}{} (parallelism {:.1}x)",
+            seconds(current.cpu_ms),
+            delta(current.cpu_ms, previous.map(|p| p.cpu_ms)),
+            parallelism
+        )
+    );
+
+    output.push_str("\n  Slowest outputs:");
+    let mut slowest: Vec<(&String, &u64)> = current.slowest.iter().collect();
+    slowest.sort_by_key(|(_, duration)| std::cmp::Reverse(**duration));
+    for (name, duration) in slowest.iter().take(top) {
+        let previous_duration = previous.and_then(|p| p.slowest.get(*name).copied());
+        output.push_str(
+            &format!(
+                "\n    {:>9}{}  {}",
+                seconds(**duration),
+                delta(**duration, previous_duration),
+                name
+            )
+        );
+    }
+
+    output.push_str("\n  Slowest targets:");
+    let mut targets: Vec<(&String, &u64)> = current.targets.iter().collect();
+    targets.sort_by_key(|(_, duration)| std::cmp::Reverse(**duration));
+    for (name, duration) in targets.iter().take(top) {
+        let previous_duration = previous.and_then(|p| p.targets.get(*name).copied());
+        output.push_str(
+            &format!(
+                "\n    {:>9}{}  {}",
+                seconds(**duration),
+                delta(**duration, previous_duration),
+                name
+            )
+        );
+    }
+
+    if previous.is_none() {
+        output.push_str("\n  No previous build recorded to compare against.");
+    }
+
+    info!("{}", output);
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.2}s", (ms as f64) / 1000.0)
+}
+
+fn delta(current: u64, previous: Option<u64>) -> String {
+    match previous {
+        Some(previous) => format!(" ({:+.2}s)", ((current as f64) - (previous as f64)) / 1000.0),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,70 @@
+use spdlog::prelude::*;
+
+use crate::commands::{ TemplatesArgs, TemplatesCommands, TemplatesListArgs };
+use crate::internal::settings::Settings;
+use crate::internal::templates;
+
+pub fn run(args: TemplatesArgs) {
+    debug!("Running the Templates command with arguments: {:#?}", args);
+
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    match args.command {
+        TemplatesCommands::List(list_args) => list(&settings, list_args),
+    }
+}
+
+fn list(settings: &Settings, args: TemplatesListArgs) {
+    for (source, dir) in templates::search_dirs(settings) {
+        let state = if dir.is_dir() { "" } else { " (not found)" };
+        info!("{} templates: {}{}", source.name(), dir.display(), state);
+    }
+
+    let templates = templates::project_templates(settings);
+    let name_width = templates
+        .iter()
+        .map(|template| template.name.len())
+        .max()
+        .unwrap_or(0);
+
+    info!("Templates:");
+    for template in &templates {
+        let overrides = if template.overrides.is_empty() {
+            String::new()
+        } else {
+            let sources: Vec<&str> = template.overrides
+                .iter()
+                .map(|source| source.name())
+                .collect();
+            format!(" (overrides {})", sources.join(", "))
+        };
+        info!(
+            "    {:<width$}  [{}] {}{}",
+            template.name,
+            template.source.name(),
+            template.metadata.description,
+            overrides,
+            width = name_width
+        );
+
+        if args.variables {
+            for (key, value) in &template.metadata.variables {
+                info!("        {} = '{}'", key, value);
+            }
+        }
+    }
+}
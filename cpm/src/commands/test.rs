@@ -0,0 +1,170 @@
+use spdlog::prelude::*;
+use std::path::Path;
+
+use crate::commands::TestArgs;
+use crate::commands::build::{ build_cmake_project, resolve_build_type };
+use crate::errors::errors::RuntimeErrors;
+use crate::internal::settings::Settings;
+use crate::internal::cmd;
+
+#[derive(Debug, Default)]
+struct TestSummary {
+    total: usize,
+    failed: usize,
+    // Test names with the reason CTest reports, i.e. "foo (Failed)"
+    failed_tests: Vec<String>,
+}
+
+pub fn run(args: TestArgs) {
+    debug!("Running the Test command with arguments: {:#?}", args);
+
+    // Grab the settings file as it will be needed for the subcommands.
+    let settings_path = match Settings::get_settings_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Failed to get settings path: {}", e);
+            return;
+        }
+    };
+
+    let settings = match Settings::load(&settings_path) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load settings: {}", e);
+            return;
+        }
+    };
+
+    // If not initialized, throw an error
+    if !settings.initialized {
+        RuntimeErrors::ProjectNotInitialized.exit();
+    }
+
+    let build_type = resolve_build_type(args.debug_build_type, args.release_build_type, &settings);
+    info!("Build Type: {}", build_type);
+
+    if args.build_project {
+        // A failed build would test the binaries of the previous build
+        let output = build_cmake_project(&settings, &build_type);
+        if output.status != 0 {
+            RuntimeErrors::BuildStepFailed("build".to_string(), output.status).exit();
+        }
+    }
+
+    let output = cmd::execute_and_capture_output(ctest_command(&settings, &args, &build_type));
+    info!("{}", output.stdout);
+
+    let summary = parse_summary(&output.stdout);
+    if summary.total == 0 {
+        warn!(
+            "No tests were found. Make sure the project calls 'enable_testing()' and 'add_test()'."
+        );
+    } else {
+        info!(
+            "Tests: {} passed, {} failed, {} total",
+            summary.total - summary.failed,
+            summary.failed,
+            summary.total
+        );
+        for failed_test in &summary.failed_tests {
+            error!("FAILED: {}", failed_test);
+        }
+    }
+
+    if summary.failed > 0 {
+        RuntimeErrors::TestsFailed(summary.failed).exit();
+    }
+    // CTest can also fail without failing tests, i.e. when a test executable is missing.
+    if output.status != 0 {
+        RuntimeErrors::CTestFailed(output.status).exit();
+    }
+}
+
+fn ctest_command(settings: &Settings, args: &TestArgs, build_type: &str) -> Vec<String> {
+    let mut ctest = vec![
+        "ctest".to_string(),
+        "--test-dir".to_string(),
+        settings.build_dir.clone(),
+        "-C".to_string(),
+        build_type.to_string(),
+        "--output-on-failure".to_string()
+    ];
+
+    if let Some(regex) = &args.tests_regex {
+        ctest.extend(["-R".to_string(), regex.clone()]);
+    }
+    if let Some(regex) = &args.exclude_regex {
+        ctest.extend(["-E".to_string(), regex.clone()]);
+    }
+    if let Some(regex) = &args.label_regex {
+        ctest.extend(["-L".to_string(), regex.clone()]);
+    }
+    if let Some(regex) = &args.label_exclude {
+        ctest.extend(["-LE".to_string(), regex.clone()]);
+    }
+    if let Some(jobs) = args.jobs {
+        ctest.extend(["-j".to_string(), jobs.to_string()]);
+    }
+    if args.rerun_failed {
+        ctest.push("--rerun-failed".to_string());
+    }
+    if let Some(timeout) = args.timeout {
+        ctest.extend(["--timeout".to_string(), timeout.to_string()]);
+    }
+    if let Some(maybe_junit) = &args.junit {
+        let junit_path = match maybe_junit {
+            Some(path) if !path.trim().is_empty() => Path::new(&settings.working_dir).join(path),
+            _ => Path::new(&settings.build_dir).join(format!("junit-{}.xml", build_type)),
+        };
+        info!("Writing JUnit report to: {}", junit_path.display());
+        ctest.extend(["--output-junit".to_string(), junit_path.to_str().unwrap().to_string()]);
+    }
+
+    ctest
+}
+
+// Parses the summary CTest prints at the end of a run:
+//     67% tests passed, 1 tests failed out of 3
+//     ...
+//     The following tests FAILED:
+//               2 - foo (Failed)
+fn parse_summary(output: &str) -> TestSummary {
+    let mut summary = TestSummary::default();
+    let mut in_failed_list = false;
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if line.contains("tests passed,") && line.contains("out of") {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if let Some(total) = words.last().and_then(|w| w.parse().ok()) {
+                summary.total = total;
+            }
+            if let Some(position) = words.iter().position(|w| *w == "failed") {
+                if position >= 2 {
+                    summary.failed = words[position - 2].parse().unwrap_or(0);
+                }
+            }
+            continue;
+        }
+
+        if line.starts_with("The following tests FAILED") {
+            in_failed_list = true;
+            continue;
+        }
+
+        if in_failed_list {
+            // Entries look like '2 - foo (Failed)', anything else ends the list
+            match line.split_once(" - ") {
+                Some((number, name)) if number.trim().parse::<usize>().is_ok() => {
+                    summary.failed_tests.push(name.trim().to_string());
+                }
+                _ => {
+                    in_failed_list = false;
+                }
+            }
+        }
+    }
+
+    summary
+}
@@ -5,6 +5,8 @@ pub enum RuntimeErrors {
     // OS related errors 1-9
     NotSupportedOS(Option<String>),
     WorkingDirSameAsExePath(String, String),
+    // Reserved, the stderr of commands is logged as is
+    #[allow(dead_code)]
     CmdCaughtStdErr(Option<String>),
     // JSON file related errors 10-10
     JSONFileNotFound(Option<String>),
@@ -26,6 +28,64 @@ pub enum RuntimeErrors {
     BuildTypeBothSet,
     InvalidCleanCommand(char),
     ProjectNotInitialized,
+    StageAssetsFailed(Option<String>),
+    UnsafeDeletePath(String, String),
+    InvalidDefinition(String),
+    // CMake File API related errors 51-60
+    FileApiReplyNotFound(Option<String>),
+    ConfigurationNotFound(String),
+    TargetNotFound(String),
+    // Run Command related errors 61-70
+    TargetNotExecutable(String),
+    ArtifactNotFound(String),
+    // Test Command related errors 71-80
+    TestsFailed(usize),
+    CTestFailed(i32),
+    // Package Command related errors 81-90
+    CPackConfigNotFound(String),
+    PackageGeneratorNotSupported(String),
+    PackageFailed(Option<String>),
+    // CMake Cache Command related errors 91-100
+    CMakeCacheNotFound(String),
+    CMakeCacheEntryNotFound(String),
+    CMakeCacheSetFailed(String, i32),
+    // Build Report related errors 101-110
+    BuildStepFailed(String, i32),
+    NinjaLogNotFound(String),
+    // Compiler Launcher related errors 111-120
+    InvalidLauncher(String),
+    LauncherNotFound(String),
+    // Build Variant related errors 121-130
+    InvalidVariant(String),
+    VariantNotSupported(String),
+    // Coverage Command related errors 131-140
+    CoverageBuildNotFound,
+    CoverageToolNotFound(String),
+    CoverageFailed(String),
+    CoverageBelowThreshold(f64, f64),
+    // New Command related errors 141-150
+    InvalidProjectName(String),
+    TemplateNotFound(String, String),
+    InvalidCxxStandard(String),
+    ScaffoldFailed(String),
+    InvalidTemplateVariable(String),
+    // Add Command related errors 151-160
+    InvalidTargetName(String),
+    CMakeListsNotFound(String),
+    UnsafeCMakeListsEdit(String, String),
+    AddTargetFailed(String),
+    // Package Config related errors 161-170
+    PackageNotInConfig(String),
+    InstallConfigEditFailed(String, String),
+    PackageUsageNotFound(String),
+    TargetDefinitionNotFound(String),
+    // Init related errors 171-180
+    InitializedElsewhere(String, String),
+    DeinitFailed(String),
+    InvalidEditorConfig(String),
+    // Project Root related errors 181-190
+    ProjectRootMismatch(String, String),
+    DirectoryNotAccessible(String, String),
     // Not implemented 1000-1005
     NotImplemented,
 }
@@ -57,6 +117,64 @@ impl RuntimeErrors {
             RuntimeErrors::BuildTypeBothSet => 45,
             RuntimeErrors::InvalidCleanCommand(_) => 46,
             RuntimeErrors::ProjectNotInitialized => 47,
+            RuntimeErrors::StageAssetsFailed(_) => 48,
+            RuntimeErrors::UnsafeDeletePath(_, _) => 49,
+            RuntimeErrors::InvalidDefinition(_) => 50,
+            // CMake File API related errors 51-60
+            RuntimeErrors::FileApiReplyNotFound(_) => 51,
+            RuntimeErrors::ConfigurationNotFound(_) => 52,
+            RuntimeErrors::TargetNotFound(_) => 53,
+            // Run Command related errors 61-70
+            RuntimeErrors::TargetNotExecutable(_) => 61,
+            RuntimeErrors::ArtifactNotFound(_) => 62,
+            // Test Command related errors 71-80
+            RuntimeErrors::TestsFailed(_) => 71,
+            RuntimeErrors::CTestFailed(_) => 72,
+            // Package Command related errors 81-90
+            RuntimeErrors::CPackConfigNotFound(_) => 81,
+            RuntimeErrors::PackageGeneratorNotSupported(_) => 82,
+            RuntimeErrors::PackageFailed(_) => 83,
+            // CMake Cache Command related errors 91-100
+            RuntimeErrors::CMakeCacheNotFound(_) => 91,
+            RuntimeErrors::CMakeCacheEntryNotFound(_) => 92,
+            RuntimeErrors::CMakeCacheSetFailed(_, _) => 93,
+            // Build Report related errors 101-110
+            RuntimeErrors::BuildStepFailed(_, _) => 101,
+            RuntimeErrors::NinjaLogNotFound(_) => 102,
+            // Compiler Launcher related errors 111-120
+            RuntimeErrors::InvalidLauncher(_) => 111,
+            RuntimeErrors::LauncherNotFound(_) => 112,
+            // Build Variant related errors 121-130
+            RuntimeErrors::InvalidVariant(_) => 121,
+            RuntimeErrors::VariantNotSupported(_) => 122,
+            // Coverage Command related errors 131-140
+            RuntimeErrors::CoverageBuildNotFound => 131,
+            RuntimeErrors::CoverageToolNotFound(_) => 132,
+            RuntimeErrors::CoverageFailed(_) => 133,
+            RuntimeErrors::CoverageBelowThreshold(_, _) => 134,
+            // New Command related errors 141-150
+            RuntimeErrors::InvalidProjectName(_) => 141,
+            RuntimeErrors::TemplateNotFound(_, _) => 142,
+            RuntimeErrors::InvalidCxxStandard(_) => 143,
+            RuntimeErrors::ScaffoldFailed(_) => 144,
+            RuntimeErrors::InvalidTemplateVariable(_) => 145,
+            // Add Command related errors 151-160
+            RuntimeErrors::InvalidTargetName(_) => 151,
+            RuntimeErrors::CMakeListsNotFound(_) => 152,
+            RuntimeErrors::UnsafeCMakeListsEdit(_, _) => 153,
+            RuntimeErrors::AddTargetFailed(_) => 154,
+            // Package Config related errors 161-170
+            RuntimeErrors::PackageNotInConfig(_) => 161,
+            RuntimeErrors::InstallConfigEditFailed(_, _) => 162,
+            RuntimeErrors::PackageUsageNotFound(_) => 163,
+            RuntimeErrors::TargetDefinitionNotFound(_) => 164,
+            // Init related errors 171-180
+            RuntimeErrors::InitializedElsewhere(_, _) => 171,
+            RuntimeErrors::DeinitFailed(_) => 172,
+            RuntimeErrors::InvalidEditorConfig(_) => 173,
+            // Project Root related errors 181-190
+            RuntimeErrors::ProjectRootMismatch(_, _) => 181,
+            RuntimeErrors::DirectoryNotAccessible(_, _) => 182,
             // Not implemented 1000-1005
             RuntimeErrors::NotImplemented => 1000,
         }
@@ -182,6 +300,280 @@ impl RuntimeErrors {
                     self.error_code()
                 )
             }
+            RuntimeErrors::StageAssetsFailed(Some(message)) => {
+                format!("|Error {}| Failed to stage runtime assets: {}", self.error_code(), message)
+            }
+            RuntimeErrors::StageAssetsFailed(None) => {
+                format!("|Error {}| Failed to stage runtime assets", self.error_code())
+            }
+            RuntimeErrors::UnsafeDeletePath(path, reason) => {
+                format!(
+                    "|Error {}| Refusing to delete '{}': {}",
+                    self.error_code(),
+                    path,
+                    reason
+                )
+            }
+            RuntimeErrors::InvalidDefinition(definition) => {
+                format!(
+                    "|Error {}| Invalid definition '{}', expected KEY=VALUE or KEY:TYPE=VALUE",
+                    self.error_code(),
+                    definition
+                )
+            }
+            // CMake File API related errors 51-60
+            RuntimeErrors::FileApiReplyNotFound(Some(message)) => {
+                format!(
+                    "|Error {}| CMake File API reply not found, generate the project with 'build -g' first: {}",
+                    self.error_code(),
+                    message
+                )
+            }
+            RuntimeErrors::FileApiReplyNotFound(None) => {
+                format!(
+                    "|Error {}| CMake File API reply not found, generate the project with 'build -g' first",
+                    self.error_code()
+                )
+            }
+            RuntimeErrors::ConfigurationNotFound(config) => {
+                format!(
+                    "|Error {}| Configuration '{}' not found in the generated project",
+                    self.error_code(),
+                    config
+                )
+            }
+            RuntimeErrors::TargetNotFound(target) => {
+                format!(
+                    "|Error {}| Target '{}' not found in the generated project",
+                    self.error_code(),
+                    target
+                )
+            }
+            // Run Command related errors 61-70
+            RuntimeErrors::TargetNotExecutable(target) => {
+                format!("|Error {}| Target '{}' is not an executable", self.error_code(), target)
+            }
+            RuntimeErrors::ArtifactNotFound(artifact) => {
+                format!(
+                    "|Error {}| Artifact '{}' not found, build the target first",
+                    self.error_code(),
+                    artifact
+                )
+            }
+            // Test Command related errors 71-80
+            RuntimeErrors::TestsFailed(count) => {
+                format!("|Error {}| {} test(s) failed", self.error_code(), count)
+            }
+            RuntimeErrors::CTestFailed(status) => {
+                format!("|Error {}| CTest exited with code {}", self.error_code(), status)
+            }
+            // Package Command related errors 81-90
+            RuntimeErrors::CPackConfigNotFound(path) => {
+                format!(
+                    "|Error {}| CPack config '{}' not found, add 'include(CPack)' to CMakeLists.txt and generate the project",
+                    self.error_code(),
+                    path
+                )
+            }
+            RuntimeErrors::PackageGeneratorNotSupported(generator) => {
+                format!(
+                    "|Error {}| CPack generator '{}' is not supported on this platform",
+                    self.error_code(),
+                    generator
+                )
+            }
+            RuntimeErrors::PackageFailed(Some(message)) => {
+                format!("|Error {}| Packaging failed: {}", self.error_code(), message)
+            }
+            RuntimeErrors::PackageFailed(None) => {
+                format!("|Error {}| Packaging failed", self.error_code())
+            }
+            // CMake Cache Command related errors 91-100
+            RuntimeErrors::CMakeCacheNotFound(path) => {
+                format!(
+                    "|Error {}| CMake cache '{}' not found, generate the project with 'build -g' first",
+                    self.error_code(),
+                    path
+                )
+            }
+            RuntimeErrors::CMakeCacheEntryNotFound(key) => {
+                format!("|Error {}| CMake cache entry '{}' not found", self.error_code(), key)
+            }
+            RuntimeErrors::CMakeCacheSetFailed(definition, status) => {
+                format!(
+                    "|Error {}| CMake configure with '{}' failed with code {}. CMake may have cached the value anyway, re-run configure after fixing it",
+                    self.error_code(),
+                    definition,
+                    status
+                )
+            }
+            // Build Report related errors 101-110
+            RuntimeErrors::BuildStepFailed(step, status) => {
+                format!("|Error {}| The {} step failed with code {}", self.error_code(), step, status)
+            }
+            RuntimeErrors::NinjaLogNotFound(path) => {
+                format!(
+                    "|Error {}| Ninja build log '{}' not found, build the project with a Ninja generator first",
+                    self.error_code(),
+                    path
+                )
+            }
+            // Compiler Launcher related errors 111-120
+            RuntimeErrors::InvalidLauncher(launcher) => {
+                format!(
+                    "|Error {}| Invalid compiler launcher '{}', expected 'auto', 'ccache', 'sccache' or 'none'",
+                    self.error_code(),
+                    launcher
+                )
+            }
+            RuntimeErrors::LauncherNotFound(launcher) => {
+                format!("|Error {}| Compiler launcher '{}' not found on PATH", self.error_code(), launcher)
+            }
+            // Build Variant related errors 121-130
+            RuntimeErrors::InvalidVariant(reason) => {
+                format!("|Error {}| Invalid build variant: {}", self.error_code(), reason)
+            }
+            RuntimeErrors::VariantNotSupported(reason) => {
+                format!("|Error {}| Build variant not supported: {}", self.error_code(), reason)
+            }
+            // Coverage Command related errors 131-140
+            RuntimeErrors::CoverageBuildNotFound => {
+                format!(
+                    "|Error {}| The active build is not a coverage build, generate it with 'build -g <SYSTEM_TYPE> --coverage' first",
+                    self.error_code()
+                )
+            }
+            RuntimeErrors::CoverageToolNotFound(tool) => {
+                format!("|Error {}| Coverage tool '{}' not found on PATH", self.error_code(), tool)
+            }
+            RuntimeErrors::CoverageFailed(reason) => {
+                format!("|Error {}| Coverage failed: {}", self.error_code(), reason)
+            }
+            RuntimeErrors::CoverageBelowThreshold(total, minimum) => {
+                format!(
+                    "|Error {}| Line coverage {:.2}% is below the minimum of {:.2}%",
+                    self.error_code(),
+                    total,
+                    minimum
+                )
+            }
+            // New Command related errors 141-150
+            RuntimeErrors::InvalidProjectName(name) => {
+                format!(
+                    "|Error {}| Invalid project name '{}', only letters, digits, '_' and '-' are allowed",
+                    self.error_code(),
+                    name
+                )
+            }
+            RuntimeErrors::TemplateNotFound(name, available) => {
+                format!(
+                    "|Error {}| Template '{}' not found. Available templates: {}",
+                    self.error_code(),
+                    name,
+                    available
+                )
+            }
+            RuntimeErrors::InvalidCxxStandard(standard) => {
+                format!(
+                    "|Error {}| Invalid C++ standard '{}', expected 11, 14, 17, 20, 23 or 26",
+                    self.error_code(),
+                    standard
+                )
+            }
+            RuntimeErrors::ScaffoldFailed(reason) => {
+                format!("|Error {}| Failed to create the project: {}", self.error_code(), reason)
+            }
+            RuntimeErrors::InvalidTemplateVariable(define) => {
+                format!(
+                    "|Error {}| Invalid template variable '{}', expected KEY=VALUE",
+                    self.error_code(),
+                    define
+                )
+            }
+            // Add Command related errors 151-160
+            RuntimeErrors::InvalidTargetName(name) => {
+                format!(
+                    "|Error {}| Invalid target name '{}', only letters, digits, '_' and '-' are allowed",
+                    self.error_code(),
+                    name
+                )
+            }
+            RuntimeErrors::CMakeListsNotFound(path) => {
+                format!("|Error {}| No CMakeLists.txt found in: {}", self.error_code(), path)
+            }
+            RuntimeErrors::UnsafeCMakeListsEdit(path, reason) => {
+                format!(
+                    "|Error {}| Refusing to edit '{}': {}. Add the add_subdirectory call manually.",
+                    self.error_code(),
+                    path,
+                    reason
+                )
+            }
+            RuntimeErrors::AddTargetFailed(reason) => {
+                format!("|Error {}| Failed to add to the project: {}", self.error_code(), reason)
+            }
+            // Package Config related errors 161-170
+            RuntimeErrors::PackageNotInConfig(package) => {
+                format!(
+                    "|Error {}| Package '{}' is not listed in the install config",
+                    self.error_code(),
+                    package
+                )
+            }
+            RuntimeErrors::InstallConfigEditFailed(path, reason) => {
+                format!(
+                    "|Error {}| Failed to edit the install config '{}': {}",
+                    self.error_code(),
+                    path,
+                    reason
+                )
+            }
+            RuntimeErrors::PackageUsageNotFound(package) => {
+                format!(
+                    "|Error {}| Could not find the CMake usage of package '{}'. Install it first (--install) or add the find_package call manually.",
+                    self.error_code(),
+                    package
+                )
+            }
+            RuntimeErrors::TargetDefinitionNotFound(target) => {
+                format!(
+                    "|Error {}| Target '{}' is not defined in any CMakeLists.txt of the project",
+                    self.error_code(),
+                    target
+                )
+            }
+            // Init related errors 171-180
+            RuntimeErrors::InitializedElsewhere(working_dir, current_dir) => {
+                format!(
+                    "|Error {}| CPM is already initialized for another project: {} (current directory: {}). Run 'cpm deinit' first.",
+                    self.error_code(),
+                    working_dir,
+                    current_dir
+                )
+            }
+            RuntimeErrors::DeinitFailed(reason) => {
+                format!("|Error {}| Failed to deinitialize the project: {}", self.error_code(), reason)
+            }
+            RuntimeErrors::InvalidEditorConfig(name) => {
+                format!(
+                    "|Error {}| Unknown editor configuration file '{}'. Expected clang-format, clang-tidy, editorconfig or all.",
+                    self.error_code(),
+                    name
+                )
+            }
+            // Project Root related errors 181-190
+            RuntimeErrors::ProjectRootMismatch(project_root, working_dir) => {
+                format!(
+                    "|Error {}| The project {} is not the initialized project {}. Use '-C {}' to run against the initialized project, 'cpm init --repair' if it was moved here, or 'cpm deinit' to switch projects.",
+                    self.error_code(),
+                    project_root,
+                    working_dir,
+                    working_dir
+                )
+            }
+            RuntimeErrors::DirectoryNotAccessible(dir, reason) => {
+                format!("|Error {}| Cannot change to directory '{}': {}", self.error_code(), dir, reason)
+            }
             // Not implemented 1000-1005
             RuntimeErrors::NotImplemented => {
                 format!("|Error {}| This feature is not implemented", self.error_code())
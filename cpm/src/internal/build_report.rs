@@ -0,0 +1,105 @@
+use serde::Serialize;
+use spdlog::prelude::*;
+use std::fs::File;
+use std::io::{ self, Write };
+use std::path::Path;
+use std::time::Instant;
+
+use crate::internal::cmd::CommandOutput;
+use crate::internal::diagnostics::{ self, Diagnostic, DiagnosticSummary };
+
+// Timings and diagnostics of the configure, build and install steps of a single 'build' invocation.
+#[derive(Serialize, Debug, Default)]
+pub struct BuildReport {
+    pub build_type: String,
+    pub steps: Vec<StepReport>,
+    pub summary: DiagnosticSummary,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StepReport {
+    // "configure", "build" or "install"
+    pub name: String,
+    pub seconds: f64,
+    // None if the step was skipped (i.e. configure inputs unchanged)
+    pub status: Option<i32>,
+}
+
+impl BuildReport {
+    pub fn new(build_type: &str) -> Self {
+        BuildReport {
+            build_type: build_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // Records a step started at 'started' and collects the diagnostics from its output.
+    pub fn add_step(&mut self, name: &str, started: Instant, output: Option<&CommandOutput>) {
+        self.steps.push(StepReport {
+            name: name.to_string(),
+            seconds: started.elapsed().as_secs_f64(),
+            status: output.map(|o| o.status),
+        });
+
+        if let Some(output) = output {
+            for diagnostic in diagnostics::parse(&format!("{}\n{}", output.stdout, output.stderr)) {
+                if !self.diagnostics.contains(&diagnostic) {
+                    self.diagnostics.push(diagnostic);
+                }
+            }
+            self.summary = diagnostics::summarize(&self.diagnostics);
+        }
+    }
+
+    // The first step that finished with a non zero exit code.
+    pub fn failed_step(&self) -> Option<&StepReport> {
+        self.steps.iter().find(|step| matches!(step.status, Some(status) if status != 0))
+    }
+
+    pub fn print(&self) {
+        info!("Build summary ({}):", self.build_type);
+        for step in &self.steps {
+            let result = match step.status {
+                Some(0) => "ok".to_string(),
+                Some(status) => format!("failed with code {}", status),
+                None => "skipped".to_string(),
+            };
+            info!("    {:<10} {:>8.2}s  {}", step.name, step.seconds, result);
+        }
+
+        info!("    {} warning(s), {} error(s)", self.summary.warnings, self.summary.errors);
+
+        // Noisiest files first
+        let mut files: Vec<_> = self.summary.files.iter().collect();
+        files.sort_by_key(|(_, counts)| std::cmp::Reverse((counts.errors, counts.warnings)));
+        for (file, counts) in files.iter().take(10) {
+            info!("    {}: {} warning(s), {} error(s)", file, counts.warnings, counts.errors);
+        }
+        if files.len() > 10 {
+            info!("    ... and {} more file(s)", files.len() - 10);
+        }
+
+        if let Some(first_error) = &self.summary.first_error {
+            error!("First error: {}: {}", location(first_error), first_error.message);
+        }
+    }
+
+    pub fn write_json(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+        let mut file = File::create(path)?;
+        file.write_all(contents.as_bytes())
+    }
+}
+
+// <file>[:<line>[:<column>]]
+fn location(diagnostic: &Diagnostic) -> String {
+    match (diagnostic.line, diagnostic.column) {
+        (Some(line), Some(column)) => format!("{}:{}:{}", diagnostic.file, line, column),
+        (Some(line), None) => format!("{}:{}", diagnostic.file, line),
+        _ => diagnostic.file.clone(),
+    }
+}
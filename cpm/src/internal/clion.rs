@@ -0,0 +1,229 @@
+use std::path::Path;
+
+use crate::internal::ide_profile::{ self, CMakeProfile };
+
+// CLion CMake profiles are shared through '<project>/.idea/cmake.xml':
+//     <component name="CMakeSharedSettings">
+//       <configurations>
+//         <configuration PROFILE_NAME="cpm-msvc-Debug" ENABLED="true" CONFIG_NAME="Debug" ... />
+//       </configurations>
+//     </component>
+// The file is edited as text, lines of 'cpm-...' profiles are replaced and everything else is kept.
+pub const IDEA_DIR: &str = ".idea";
+pub const CMAKE_FILE: &str = "cmake.xml";
+const CONFIGURATIONS_START: &str = "<configurations>";
+const CONFIGURATIONS_END: &str = "</configurations>";
+const CONFIGURATIONS_EMPTY: &str = "<configurations />";
+// CLion's name of its bundled MSVC toolchain
+const MSVC_TOOLCHAIN: &str = "Visual Studio";
+
+// Generation directory relative to the project, other paths as they are. Always with '/'.
+pub fn generation_dir(project_root: &Path, path: &Path) -> String {
+    match path.strip_prefix(project_root) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => path.to_string_lossy().replace('\\', "/"),
+    }
+}
+
+// CMake options of the profile, quoted where they contain spaces.
+pub fn generation_options(profile: &CMakeProfile) -> String {
+    let mut options: Vec<String> = vec!["-G".to_string(), quote(&profile.generator)];
+    for definition in &profile.definitions {
+        options.push(quote(&format!("-D{}", definition)));
+    }
+    options.join(" ")
+}
+
+pub fn configuration_line(profile: &CMakeProfile, project_root: &Path, indent: &str) -> String {
+    let mut attributes = vec![
+        ("PROFILE_NAME", profile.name()),
+        ("ENABLED", "true".to_string()),
+        ("CONFIG_NAME", profile.build_type.clone()),
+        ("GENERATION_DIR", generation_dir(project_root, &profile.build_dir))
+    ];
+    // The other system types use the default toolchain, with the compilers from the options
+    if profile.is_msvc() {
+        attributes.push(("TOOLCHAIN_NAME", MSVC_TOOLCHAIN.to_string()));
+    }
+    attributes.push(("GENERATION_OPTIONS", generation_options(profile)));
+
+    let attributes: Vec<String> = attributes
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape(value)))
+        .collect();
+    format!("{}<configuration {} />", indent, attributes.join(" "))
+}
+
+pub fn document(profiles: &[CMakeProfile], project_root: &Path) -> String {
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+        "<project version=\"4\">".to_string(),
+        "  <component name=\"CMakeSharedSettings\">".to_string(),
+        format!("    {}", CONFIGURATIONS_START)
+    ];
+    for profile in profiles {
+        lines.push(configuration_line(profile, project_root, "      "));
+    }
+    lines.push(format!("    {}", CONFIGURATIONS_END));
+    lines.push("  </component>".to_string());
+    lines.push("</project>".to_string());
+    format!("{}\n", lines.join("\n"))
+}
+
+// Replaces the 'cpm-...' profiles of an existing file. Returns None if it has no configurations
+// list to add them to.
+pub fn merge(contents: &str, profiles: &[CMakeProfile], project_root: &Path) -> Option<String> {
+    let mut lines: Vec<String> = contents
+        .lines()
+        .filter(|line| !is_generated_line(line))
+        .map(|line| line.to_string())
+        .collect();
+
+    // An empty list is written as '<configurations />' by CLion
+    if let Some(empty) = lines.iter().position(|line| line.trim() == CONFIGURATIONS_EMPTY) {
+        let indent = leading_whitespace(&lines[empty]).to_string();
+        lines.splice(
+            empty..=empty,
+            [
+                format!("{}{}", indent, CONFIGURATIONS_START),
+                format!("{}{}", indent, CONFIGURATIONS_END),
+            ]
+        );
+    }
+    let end = lines.iter().position(|line| line.trim() == CONFIGURATIONS_END)?;
+
+    // Profiles are indented one level deeper than the closing tag
+    let indent = format!("{}  ", leading_whitespace(&lines[end]));
+    let generated: Vec<String> = profiles
+        .iter()
+        .map(|profile| configuration_line(profile, project_root, &indent))
+        .collect();
+    lines.splice(end..end, generated);
+
+    let line_ending = if contents.contains("\r\n") { "\r\n" } else { "\n" };
+    Some(format!("{}{}", lines.join(line_ending), line_ending))
+}
+
+fn is_generated_line(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("<configuration ") &&
+        line.contains(&format!("PROFILE_NAME=\"{}", ide_profile::PREFIX))
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn quote(option: &str) -> String {
+    if option.contains(' ') { format!("\"{}\"", option) } else { option.to_string() }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::configure_command::Definition;
+    use std::path::PathBuf;
+
+    fn profile(id: &str, system_type: &str, definitions: &[&str]) -> CMakeProfile {
+        CMakeProfile {
+            id: id.to_string(),
+            system_type: system_type.to_string(),
+            build_type: "Debug".to_string(),
+            generator: ide_profile::GENERATOR.to_string(),
+            definitions: definitions
+                .iter()
+                .filter_map(|definition| Definition::parse(definition))
+                .collect(),
+            build_dir: PathBuf::from("C:/project/Build/ide/").join(id),
+            install_dir: PathBuf::from("C:/project/Install"),
+        }
+    }
+
+    fn root() -> PathBuf {
+        PathBuf::from("C:/project")
+    }
+
+    fn profile_for_gcc() -> CMakeProfile {
+        profile("gcc-Debug", "unix/gcc", &["CMAKE_CXX_COMPILER=g++"])
+    }
+
+    const USER_PROFILE: &str =
+        "      <configuration PROFILE_NAME=\"Debug\" ENABLED=\"true\" CONFIG_NAME=\"Debug\" />";
+
+    #[test]
+    fn writes_profiles_with_escaped_options() {
+        let profile = profile("msvc-Debug", "nt/msvc", &["FLAGS:STRING=-DA=\"1\" <b> & c", "X=1"]);
+        let line = configuration_line(&profile, &root(), "  ");
+        assert_eq!(
+            line,
+            "  <configuration PROFILE_NAME=\"cpm-msvc-Debug\" ENABLED=\"true\" CONFIG_NAME=\"Debug\" \
+             GENERATION_DIR=\"Build/ide/msvc-Debug\" TOOLCHAIN_NAME=\"Visual Studio\" \
+             GENERATION_OPTIONS=\"-G Ninja &quot;-DFLAGS:STRING=-DA=&quot;1&quot; &lt;b&gt; &amp; c&quot; -DX=1\" />"
+        );
+
+        // The other system types use the default toolchain
+        let line = configuration_line(&profile_for_gcc(), &root(), "");
+        assert!(!line.contains("TOOLCHAIN_NAME"));
+    }
+
+    #[test]
+    fn replaces_generated_profiles_and_keeps_user_profiles() {
+        let contents = format!(
+            "<project version=\"4\">\n  <component name=\"CMakeSharedSettings\">\n    <configurations>\n\
+             {}\n      <configuration PROFILE_NAME=\"cpm-old-Debug\" ENABLED=\"true\" />\n\
+             \x20   </configurations>\n  </component>\n</project>\n",
+            USER_PROFILE
+        );
+        let merged = merge(&contents, &[profile_for_gcc()], &root()).unwrap();
+        assert!(merged.contains(USER_PROFILE));
+        assert!(!merged.contains("cpm-old-Debug"));
+        assert_eq!(merged.matches("PROFILE_NAME=\"cpm-gcc-Debug\"").count(), 1);
+        assert!(merged.contains("\n      <configuration PROFILE_NAME=\"cpm-gcc-Debug\""));
+
+        // Merging again gives the same file
+        assert_eq!(merge(&merged, &[profile_for_gcc()], &root()).unwrap(), merged);
+    }
+
+    #[test]
+    fn expands_an_empty_configurations_list() {
+        let contents =
+            "<project version=\"4\">\n  <component name=\"CMakeSharedSettings\">\n    \
+             <configurations />\n  </component>\n</project>\n";
+        let merged = merge(contents, &[profile_for_gcc()], &root()).unwrap();
+        assert!(
+            merged.contains(
+                "    <configurations>\n      <configuration PROFILE_NAME=\"cpm-gcc-Debug\""
+            )
+        );
+        assert!(merged.contains("/>\n    </configurations>\n  </component>"));
+    }
+
+    #[test]
+    fn refuses_files_without_a_configurations_list() {
+        let contents = "<project version=\"4\">\n</project>\n";
+        assert_eq!(merge(contents, &[profile_for_gcc()], &root()), None);
+    }
+
+    #[test]
+    fn preserves_crlf_line_endings() {
+        let contents = "<project>\r\n  <configurations>\r\n  </configurations>\r\n</project>\r\n";
+        let merged = merge(contents, &[profile_for_gcc()], &root()).unwrap();
+        assert_eq!(merged.matches('\n').count(), 5);
+        assert_eq!(merged.matches("\r\n").count(), 5);
+    }
+
+    #[test]
+    fn writes_a_new_document() {
+        let document = document(&[profile_for_gcc()], &root());
+        assert!(document.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert_eq!(merge(&document, &[profile_for_gcc()], &root()).unwrap(), document);
+    }
+}
@@ -0,0 +1,206 @@
+use std::fs::File;
+use std::io::{ self, Read };
+use std::path::{ Path, PathBuf };
+
+// Parser for CMake's own cache file '<build_dir>/CMakeCache.txt'. Entries look like:
+//     //Help string, can span
+//     // multiple lines
+//     KEY:TYPE=VALUE
+// Lines starting with '#' are comments. Advanced entries are marked by a separate
+// 'KEY-ADVANCED:INTERNAL=1' entry.
+pub const FILE_NAME: &str = "CMakeCache.txt";
+
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub key: String,
+    // BOOL, PATH, FILEPATH, STRING, INTERNAL, STATIC or UNINITIALIZED
+    pub entry_type: String,
+    pub value: String,
+    pub help: String,
+    pub advanced: bool,
+}
+
+impl CacheEntry {
+    // INTERNAL and STATIC entries are managed by CMake itself
+    pub fn is_internal(&self) -> bool {
+        self.entry_type == "INTERNAL" || self.entry_type == "STATIC"
+    }
+}
+
+pub fn cache_path(build_dir: &Path) -> PathBuf {
+    build_dir.join(FILE_NAME)
+}
+
+pub fn load(build_dir: &Path) -> io::Result<Vec<CacheEntry>> {
+    let mut file = File::open(cache_path(build_dir))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    Ok(parse(&contents))
+}
+
+pub fn parse(contents: &str) -> Vec<CacheEntry> {
+    let mut entries: Vec<CacheEntry> = Vec::new();
+    let mut advanced_keys: Vec<String> = Vec::new();
+    let mut help: Vec<String> = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim_end_matches('\r');
+
+        if let Some(help_line) = line.strip_prefix("//") {
+            help.push(help_line.trim().to_string());
+            continue;
+        }
+        if line.trim().is_empty() || line.starts_with('#') {
+            help.clear();
+            continue;
+        }
+
+        // The key may be quoted if it contains special characters: "KEY WITH SPACES":TYPE=VALUE
+        let (key, rest) = if let Some(quoted) = line.strip_prefix('"') {
+            match quoted.split_once('"') {
+                Some((key, rest)) => (key.to_string(), rest),
+                None => {
+                    help.clear();
+                    continue;
+                }
+            }
+        } else {
+            match line.find([':', '=']) {
+                Some(position) => (line[..position].to_string(), &line[position..]),
+                None => {
+                    help.clear();
+                    continue;
+                }
+            }
+        };
+
+        let (entry_type, value) = match rest.strip_prefix(':').and_then(|r| r.split_once('=')) {
+            Some((entry_type, value)) => (entry_type.to_string(), value.to_string()),
+            None =>
+                match rest.strip_prefix('=') {
+                    Some(value) => ("UNINITIALIZED".to_string(), value.to_string()),
+                    None => {
+                        help.clear();
+                        continue;
+                    }
+                }
+        };
+
+        if let Some(advanced_key) = key.strip_suffix("-ADVANCED") {
+            if value == "1" {
+                advanced_keys.push(advanced_key.to_string());
+            }
+        } else {
+            entries.push(CacheEntry {
+                key,
+                entry_type,
+                value,
+                help: help.join(" "),
+                advanced: false,
+            });
+        }
+        help.clear();
+    }
+
+    for entry in entries.iter_mut() {
+        entry.advanced = advanced_keys.contains(&entry.key);
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CACHE: &str = "\
+# This is the CMakeCache file.
+# For build in directory: C:/project/Build
+
+//Choose the type of build, options are: None Debug Release
+// RelWithDebInfo MinSizeRel ...
+CMAKE_BUILD_TYPE:STRING=Debug
+
+//Build the testing tree.
+BUILD_TESTING:BOOL=ON
+
+//Path to a program.
+CMAKE_AR:FILEPATH=C:/Program Files/LLVM/bin/llvm-ar.exe
+//ADVANCED property for variable: CMAKE_AR
+CMAKE_AR-ADVANCED:INTERNAL=1
+
+//Flags with an '=' in them
+CMAKE_CXX_FLAGS:STRING=-DVALUE=1 -DPATH=C:/a:b
+CMAKE_CXX_FLAGS-ADVANCED:INTERNAL=0
+
+\"KEY WITH SPACES\":STRING=a=b
+UNTYPED=value
+CMAKE_CACHEFILE_DIR:INTERNAL=c:/project/Build
+not an entry
+";
+
+    fn entry<'a>(entries: &'a [CacheEntry], key: &str) -> &'a CacheEntry {
+        entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_typed_entries_with_their_help() {
+        let entries = parse(CACHE);
+        let build_type = entry(&entries, "CMAKE_BUILD_TYPE");
+        assert_eq!(build_type.entry_type, "STRING");
+        assert_eq!(build_type.value, "Debug");
+        assert_eq!(
+            build_type.help,
+            "Choose the type of build, options are: None Debug Release RelWithDebInfo MinSizeRel ..."
+        );
+
+        let testing = entry(&entries, "BUILD_TESTING");
+        assert_eq!((testing.entry_type.as_str(), testing.value.as_str()), ("BOOL", "ON"));
+        assert_eq!(testing.help, "Build the testing tree.");
+    }
+
+    #[test]
+    fn keeps_equals_and_colons_in_values() {
+        let entries = parse(CACHE);
+        let ar = entry(&entries, "CMAKE_AR");
+        assert_eq!(ar.value, "C:/Program Files/LLVM/bin/llvm-ar.exe");
+        assert_eq!(entry(&entries, "CMAKE_CXX_FLAGS").value, "-DVALUE=1 -DPATH=C:/a:b");
+        assert_eq!(entry(&entries, "KEY WITH SPACES").value, "a=b");
+
+        let untyped = entry(&entries, "UNTYPED");
+        assert_eq!(untyped.entry_type, "UNINITIALIZED");
+        assert_eq!(untyped.value, "value");
+    }
+
+    #[test]
+    fn marks_advanced_and_internal_entries() {
+        let entries = parse(CACHE);
+        assert!(entry(&entries, "CMAKE_AR").advanced);
+        assert!(!entry(&entries, "CMAKE_CXX_FLAGS").advanced);
+        assert!(!entry(&entries, "CMAKE_BUILD_TYPE").advanced);
+        assert!(entry(&entries, "CMAKE_CACHEFILE_DIR").is_internal());
+        assert!(!entry(&entries, "CMAKE_BUILD_TYPE").is_internal());
+
+        // The -ADVANCED markers are not entries, comments and invalid lines neither
+        assert!(entries.iter().all(|entry| !entry.key.ends_with("-ADVANCED")));
+        assert_eq!(entries.len(), 7);
+    }
+
+    #[test]
+    fn comments_reset_the_help() {
+        let entries = parse("//Stale help\n# comment\nA:BOOL=OFF\n");
+        assert_eq!(entry(&entries, "A").help, "");
+    }
+
+    #[test]
+    fn parses_crlf_input() {
+        let entries = parse(&CACHE.replace('\n', "\r\n"));
+        assert_eq!(entries.len(), 7);
+        assert_eq!(entry(&entries, "CMAKE_BUILD_TYPE").value, "Debug");
+        assert!(entry(&entries, "CMAKE_AR").advanced);
+        assert_eq!(entry(&entries, "BUILD_TESTING").help, "Build the testing tree.");
+    }
+}
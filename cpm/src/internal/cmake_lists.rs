@@ -0,0 +1,466 @@
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+use walkdir::WalkDir;
+
+// Minimal reader for CMake listfiles (CMakeLists.txt), enough to find commands and where they end:
+//     command_name(arguments ...)   # comment
+// Arguments may contain quoted strings, bracket arguments '[[...]]', comments and nested parentheses.
+// The block depth tells whether a command is inside if()/foreach()/while()/function()/macro()/block().
+pub const FILE_NAME: &str = "CMakeLists.txt";
+
+#[derive(Debug, Clone)]
+pub struct Command {
+    // Lower case, CMake command names are case insensitive
+    pub name: String,
+    pub arguments: String,
+    // Byte offsets of the command name and right after the closing parenthesis
+    pub start: usize,
+    pub end: usize,
+    pub depth: usize,
+}
+
+impl Command {
+    // Arguments split on whitespace with the quotes removed. Good enough for simple calls.
+    pub fn argument_list(&self) -> Vec<String> {
+        self.arguments
+            .split_whitespace()
+            .map(|argument| argument.trim_matches('"').to_string())
+            .collect()
+    }
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+// CMakeLists.txt files of the project. Hidden directories and the excluded ones (build trees, ...) are skipped.
+pub fn project_files(root: &Path, excluded: &[PathBuf]) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let hidden = entry.depth() > 0 && entry.file_name().to_string_lossy().starts_with('.');
+            let excluded = excluded
+                .iter()
+                .any(|dir| !dir.as_os_str().is_empty() && entry.path().starts_with(dir));
+            !hidden && !excluded
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == FILE_NAME)
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+pub fn load(path: &Path) -> io::Result<(String, Vec<Command>)> {
+    let contents = fs::read_to_string(path)?;
+    let commands = parse(&contents).map_err(|e| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+    })?;
+    Ok((contents, commands))
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Command>, String> {
+    let bytes = contents.as_bytes();
+    let mut commands: Vec<Command> = Vec::new();
+    let mut depth: usize = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == b'#' {
+            i = skip_comment(bytes, i)?;
+            continue;
+        }
+        if !(c.is_ascii_alphabetic() || c == b'_') {
+            return Err(format!("unexpected '{}' on line {}", c as char, line_of(bytes, i)));
+        }
+
+        let start = i;
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+            i += 1;
+        }
+        let name = contents[start..i].to_lowercase();
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t') {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] != b'(' {
+            return Err(format!("expected '(' after '{}' on line {}", name, line_of(bytes, start)));
+        }
+
+        let arguments_start = i + 1;
+        i = skip_arguments(bytes, i)?;
+        let arguments = contents[arguments_start..i - 1].to_string();
+
+        if is_block_end(&name) {
+            if depth == 0 {
+                return Err(format!("unmatched '{}' on line {}", name, line_of(bytes, start)));
+            }
+            depth -= 1;
+        }
+        commands.push(Command { name: name.clone(), arguments, start, end: i, depth });
+        if is_block_start(&name) {
+            depth += 1;
+        }
+    }
+
+    if depth != 0 {
+        return Err("unclosed block (missing endif/endforeach/endfunction/...)".to_string());
+    }
+    Ok(commands)
+}
+
+// Offset right after the end of the line the offset is on (or the end of the contents).
+pub fn line_end(contents: &str, offset: usize) -> usize {
+    contents[offset..]
+        .find('\n')
+        .map(|i| offset + i + 1)
+        .unwrap_or(contents.len())
+}
+
+// Offset of the start of the line the offset is on.
+pub fn line_start(contents: &str, offset: usize) -> usize {
+    contents[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+// 1-based line number of the offset.
+pub fn line_number(contents: &str, offset: usize) -> usize {
+    contents[..offset].matches('\n').count() + 1
+}
+
+// Adds 'add_subdirectory(<subdirectory>)' after the last top level add_subdirectory, otherwise at the end.
+// Returns None if the subdirectory is already added. Fails when the call could not be added safely.
+pub fn insert_subdirectory(
+    contents: &str,
+    commands: &[Command],
+    subdirectory: &str
+) -> Result<Option<String>, String> {
+    let normalize = |path: &str| path.trim_start_matches("./").trim_end_matches('/').to_string();
+    let subdirectory = normalize(subdirectory);
+
+    let added = commands
+        .iter()
+        .filter(|command| command.name == "add_subdirectory")
+        .any(|command| {
+            command
+                .argument_list()
+                .first()
+                .map(|path| normalize(path) == subdirectory)
+                .unwrap_or(false)
+        });
+    if added {
+        return Ok(None);
+    }
+
+    // Anything appended after a top level return() would never run
+    if commands.iter().any(|command| command.depth == 0 && command.name == "return") {
+        return Err("it has a top level return()".to_string());
+    }
+
+    let newline = newline(contents);
+    let call = format!("add_subdirectory({}){}", subdirectory, newline);
+    let last_subdirectory = commands
+        .iter()
+        .rfind(|command| command.depth == 0 && command.name == "add_subdirectory");
+
+    let mut contents = contents.to_string();
+    match last_subdirectory {
+        Some(command) => {
+            let mut offset = line_end(&contents, command.end);
+            if offset == contents.len() && !contents.ends_with('\n') {
+                contents.push_str(newline);
+                offset = contents.len();
+            }
+            contents.insert_str(offset, &call);
+        }
+        None => {
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                contents.push_str(newline);
+            }
+            if !contents.is_empty() {
+                contents.push_str(newline);
+            }
+            contents.push_str(&call);
+        }
+    }
+    Ok(Some(contents))
+}
+
+// Adds a line before and/or after the command, indented like the command (i.e. inside an if() block).
+pub fn insert_around(
+    contents: &str,
+    command: &Command,
+    before: Option<&str>,
+    after: Option<&str>
+) -> String {
+    let newline = newline(contents);
+    let command_line = line_start(contents, command.start);
+    let indent = &contents[command_line..command.start];
+
+    // The later offset first, so the earlier one stays valid
+    let mut edited = contents.to_string();
+    if let Some(after) = after {
+        let mut offset = line_end(&edited, command.end);
+        if offset == edited.len() && !edited.ends_with('\n') {
+            edited.push_str(newline);
+            offset = edited.len();
+        }
+        edited.insert_str(offset, &format!("{}{}{}", indent, after, newline));
+    }
+    if let Some(before) = before {
+        edited.insert_str(command_line, &format!("{}{}{}", indent, before, newline));
+    }
+    edited
+}
+
+// Line ending used by the file, CRLF if any line ends with it. Added lines keep it.
+pub fn newline(contents: &str) -> &'static str {
+    if contents.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+fn is_block_start(name: &str) -> bool {
+    matches!(name, "if" | "foreach" | "while" | "function" | "macro" | "block")
+}
+
+fn is_block_end(name: &str) -> bool {
+    matches!(name, "endif" | "endforeach" | "endwhile" | "endfunction" | "endmacro" | "endblock")
+}
+
+// i points at '('. Returns the offset after the matching ')'.
+fn skip_arguments(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+    let open = i;
+    let mut nesting = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => {
+                nesting += 1;
+                i += 1;
+            }
+            b')' => {
+                nesting -= 1;
+                i += 1;
+                if nesting == 0 {
+                    return Ok(i);
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'#' => {
+                i = skip_comment(bytes, i)?;
+            }
+            b'[' => {
+                i = match bracket_length(bytes, i) {
+                    Some(equals) => skip_bracket(bytes, i, equals)?,
+                    None => i + 1,
+                };
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+
+    Err(format!("missing ')' for the command on line {}", line_of(bytes, open)))
+}
+
+// i points at '#'. Line comments end at the newline, bracket comments '#[[...]]' at the closing bracket.
+fn skip_comment(bytes: &[u8], i: usize) -> Result<usize, String> {
+    if let Some(equals) = bracket_length(bytes, i + 1) {
+        return skip_bracket(bytes, i + 1, equals);
+    }
+    let mut i = i;
+    while i < bytes.len() && bytes[i] != b'\n' {
+        i += 1;
+    }
+    Ok(i)
+}
+
+// Number of '=' of a bracket opening '[==[' at i, None if it is not one.
+fn bracket_length(bytes: &[u8], i: usize) -> Option<usize> {
+    if bytes.get(i) != Some(&b'[') {
+        return None;
+    }
+    let mut j = i + 1;
+    while bytes.get(j) == Some(&b'=') {
+        j += 1;
+    }
+    if bytes.get(j) == Some(&b'[') { Some(j - i - 1) } else { None }
+}
+
+fn skip_bracket(bytes: &[u8], i: usize, equals: usize) -> Result<usize, String> {
+    let close = format!("]{}]", "=".repeat(equals));
+    let open_length = equals + 2;
+    let rest = &bytes[i + open_length..];
+    rest.windows(close.len())
+        .position(|window| window == close.as_bytes())
+        .map(|position| i + open_length + position + close.len())
+        .ok_or(format!("unclosed bracket on line {}", line_of(bytes, i)))
+}
+
+fn line_of(bytes: &[u8], i: usize) -> usize {
+    bytes[..i.min(bytes.len())]
+        .iter()
+        .filter(|c| **c == b'\n')
+        .count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(commands: &[Command]) -> Vec<(&str, usize)> {
+        commands
+            .iter()
+            .map(|command| (command.name.as_str(), command.depth))
+            .collect()
+    }
+
+    #[test]
+    fn parses_commands_with_their_depth() {
+        let contents = "cmake_minimum_required(VERSION 3.20)\n\
+                        PROJECT(demo CXX)\n\
+                        if(WIN32)\n  add_subdirectory(win)\nendif()\n";
+        let commands = parse(contents).unwrap();
+        assert_eq!(names(&commands), vec![
+            ("cmake_minimum_required", 0),
+            ("project", 0),
+            ("if", 0),
+            ("add_subdirectory", 1),
+            ("endif", 0),
+        ]);
+        assert_eq!(&contents[commands[1].start..commands[1].end], "PROJECT(demo CXX)");
+        assert_eq!(commands[1].arguments, "demo CXX");
+    }
+
+    #[test]
+    fn skips_comments_strings_and_nested_parentheses() {
+        let contents = "# add_subdirectory(commented)\n\
+                        message(\"not_a_command() ) # \\\" \" # )\n  (nested) STATUS)\n\
+                        #[[ add_subdirectory(bracket_comment) ]]\n\
+                        set(X 1)\n";
+        let commands = parse(contents).unwrap();
+        assert_eq!(names(&commands), vec![("message", 0), ("set", 0)]);
+    }
+
+    #[test]
+    fn skips_bracket_arguments() {
+        let contents = "set(SCRIPT [=[ ) ]] endif() # ]=])\nset(Y [[)]])\n";
+        let commands = parse(contents).unwrap();
+        assert_eq!(names(&commands), vec![("set", 0), ("set", 0)]);
+        assert_eq!(commands[0].arguments, "SCRIPT [=[ ) ]] endif() # ]=]");
+        assert_eq!(commands[1].arguments, "Y [[)]]");
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(parse("endif()\n").is_err());
+        assert!(parse("if(A)\n").is_err());
+        assert!(parse("set(X 1\n").is_err());
+        assert!(parse("set(X [[1)\n").is_err());
+        assert!(parse("set X 1\n").is_err());
+    }
+
+    #[test]
+    fn inserts_after_the_last_top_level_subdirectory() {
+        let contents = "project(demo)\nadd_subdirectory(a)\n\
+                        if(B)\nadd_subdirectory(b)\nendif()\nset(X 1)\n";
+        let commands = parse(contents).unwrap();
+        let updated = insert_subdirectory(contents, &commands, "./c/").unwrap().unwrap();
+        assert_eq!(
+            updated,
+            "project(demo)\nadd_subdirectory(a)\nadd_subdirectory(c)\n\
+             if(B)\nadd_subdirectory(b)\nendif()\nset(X 1)\n"
+        );
+
+        // Parsing the result again finds the new call, so a second run does not add it twice
+        let commands = parse(&updated).unwrap();
+        assert_eq!(insert_subdirectory(&updated, &commands, "c").unwrap(), None);
+    }
+
+    #[test]
+    fn appends_when_there_is_no_subdirectory() {
+        let contents = "project(demo)";
+        let commands = parse(contents).unwrap();
+        let updated = insert_subdirectory(contents, &commands, "lib").unwrap().unwrap();
+        assert_eq!(updated, "project(demo)\n\nadd_subdirectory(lib)\n");
+
+        let updated = insert_subdirectory("", &[], "lib").unwrap().unwrap();
+        assert_eq!(updated, "add_subdirectory(lib)\n");
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let contents = "project(demo)\r\nadd_subdirectory(a)";
+        let commands = parse(contents).unwrap();
+        let updated = insert_subdirectory(contents, &commands, "b").unwrap().unwrap();
+        assert_eq!(updated, "project(demo)\r\nadd_subdirectory(a)\r\nadd_subdirectory(b)\r\n");
+
+        let contents = "project(demo)\r\n";
+        let commands = parse(contents).unwrap();
+        let updated = insert_subdirectory(contents, &commands, "b").unwrap().unwrap();
+        assert_eq!(updated, "project(demo)\r\n\r\nadd_subdirectory(b)\r\n");
+    }
+
+    #[test]
+    fn inserts_around_a_command_with_its_indentation() {
+        let contents = "if(WIN32)\n  add_executable(app main.cpp)\nendif()\n";
+        let commands = parse(contents).unwrap();
+        let updated = insert_around(
+            contents,
+            &commands[1],
+            Some("find_package(fmt CONFIG REQUIRED)"),
+            Some("target_link_libraries(app PRIVATE fmt::fmt)")
+        );
+        assert_eq!(
+            updated,
+            "if(WIN32)\n  find_package(fmt CONFIG REQUIRED)\n  add_executable(app main.cpp)\n\
+             \x20 target_link_libraries(app PRIVATE fmt::fmt)\nendif()\n"
+        );
+
+        let contents = "add_executable(app main.cpp)";
+        let commands = parse(contents).unwrap();
+        let updated = insert_around(contents, &commands[0], None, Some("target_link_libraries()"));
+        assert_eq!(updated, "add_executable(app main.cpp)\ntarget_link_libraries()\n");
+    }
+
+    #[test]
+    fn inserts_around_a_command_with_crlf_line_endings() {
+        let contents = "project(demo)\r\nadd_executable(app main.cpp)";
+        let commands = parse(contents).unwrap();
+        let updated = insert_around(contents, &commands[1], Some("find_package()"), Some("link()"));
+        assert_eq!(
+            updated,
+            "project(demo)\r\nfind_package()\r\nadd_executable(app main.cpp)\r\nlink()\r\n"
+        );
+    }
+
+    #[test]
+    fn refuses_a_top_level_return() {
+        let contents = "project(demo)\nreturn()\n";
+        let commands = parse(contents).unwrap();
+        assert!(insert_subdirectory(contents, &commands, "lib").is_err());
+
+        let contents = "project(demo)\nif(A)\nreturn()\nendif()\n";
+        let commands = parse(contents).unwrap();
+        assert!(insert_subdirectory(contents, &commands, "lib").unwrap().is_some());
+    }
+
+    #[test]
+    fn finds_line_boundaries() {
+        let contents = "a()\nbb()\ncc()";
+        assert_eq!(line_start(contents, 5), 4);
+        assert_eq!(line_end(contents, 5), 9);
+        assert_eq!(line_end(contents, 10), contents.len());
+        assert_eq!(line_number(contents, 10), 3);
+    }
+}
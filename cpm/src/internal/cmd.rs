@@ -25,7 +25,7 @@ pub fn execute_and_display_output(cmd_array: Vec<String>) {
             let (command, args) = cmd_array.split_first().unwrap();
 
             let output = Command::new("cmd")
-                .args(&["/C", command])
+                .args(["/C", command])
                 .args(args)
                 .output()
                 .expect("Failed to execute command");
@@ -33,16 +33,15 @@ pub fn execute_and_display_output(cmd_array: Vec<String>) {
             // Remove all trailing newline characters
             if !output.stdout.is_empty() {
                 let out = String::from_utf8_lossy(&output.stdout)
-                    .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                    .trim_end_matches(['\r', '\n'])
                     .to_string();
                 trace!("STDOUT:\n{}", out);
             }
             if !output.stderr.is_empty() {
                 let err = String::from_utf8_lossy(&output.stderr)
-                    .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                    .trim_end_matches(['\r', '\n'])
                     .to_string();
                 error!("STDERR:\n{}", err);
-                RuntimeErrors::CmdCaughtStdErr(Some(err));
             }
         }
         _ => {
@@ -63,7 +62,7 @@ pub fn execute(cmd_array: Vec<String>) {
             let (command, args) = cmd_array.split_first().unwrap();
 
             let output = Command::new("cmd")
-                .args(&["/C", command])
+                .args(["/C", command])
                 .args(args)
                 .output()
                 .expect("Failed to execute command");
@@ -71,16 +70,15 @@ pub fn execute(cmd_array: Vec<String>) {
             // Remove all trailing newline characters
             if !output.stdout.is_empty() {
                 let out = String::from_utf8_lossy(&output.stdout)
-                    .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                    .trim_end_matches(['\r', '\n'])
                     .to_string();
                 debug!("STDOUT:\n{}", out);
             }
             if !output.stderr.is_empty() {
                 let err = String::from_utf8_lossy(&output.stderr)
-                    .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                    .trim_end_matches(['\r', '\n'])
                     .to_string();
                 error!("STDERR:\n{}", err);
-                RuntimeErrors::CmdCaughtStdErr(Some(err));
             }
         }
         _ => {
@@ -101,7 +99,7 @@ pub fn execute_and_return_output(cmd_array: Vec<String>) -> String {
             let (command, args) = cmd_array.split_first().unwrap();
 
             let output = Command::new("cmd")
-                .args(&["/C", command])
+                .args(["/C", command])
                 .args(args)
                 .output()
                 .expect("Failed to execute command");
@@ -109,21 +107,20 @@ pub fn execute_and_return_output(cmd_array: Vec<String>) -> String {
             // Remove all trailing newline characters
             if !output.stdout.is_empty() {
                 let out = String::from_utf8_lossy(&output.stdout)
-                    .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                    .trim_end_matches(['\r', '\n'])
                     .to_string();
                 debug!("STDOUT:\n{}", out);
             }
             if !output.stderr.is_empty() {
                 let err = String::from_utf8_lossy(&output.stderr)
-                    .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                    .trim_end_matches(['\r', '\n'])
                     .to_string();
                 error!("STDERR:\n{}", err);
-                RuntimeErrors::CmdCaughtStdErr(Some(err));
             }
 
             // Before we return the string we must make sure to remove the trailing newline character (\r\n)
             String::from_utf8_lossy(&output.stdout)
-                .trim_end_matches(|c| (c == '\r' || c == '\n'))
+                .trim_end_matches(['\r', '\n'])
                 .to_string()
         }
         _ => {
@@ -133,6 +130,109 @@ pub fn execute_and_return_output(cmd_array: Vec<String>) -> String {
     }
 }
 
+// Returns the full path of a program found on PATH.
+// A missing program is a normal outcome, so the finder runs without logging its stderr.
+pub fn find_program(program: &str) -> Option<String> {
+    let finder = match std::env::consts::OS {
+        "windows" => "where",
+        _ => "which",
+    };
+    let output = Command::new(finder).arg(program).output().ok()?;
+    if !output.status.success() {
+        trace!("'{}' not found on PATH", program);
+        return None;
+    }
+    // 'where' lists every match, the first one is used when running the program
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+// Output of a finished command, for callers that need to inspect it.
+pub struct CommandOutput {
+    pub status: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+// Same as 'execute_and_display_output' but also returns the exit status and the captured output.
+pub fn execute_and_capture_output(cmd_array: Vec<String>) -> CommandOutput {
+    if cmd_array.is_empty() {
+        RuntimeErrors::NoCommandsProvided.exit();
+    }
+
+    trace!("Executing command: {}", cmd_array.join(" "));
+
+    match init(cmd_array.clone()).as_str() {
+        "windows" => {
+            let (command, args) = cmd_array.split_first().unwrap();
+
+            let output = Command::new("cmd")
+                .args(["/C", command])
+                .args(args)
+                .output()
+                .expect("Failed to execute command");
+
+            // Remove all trailing newline characters
+            let stdout = String::from_utf8_lossy(&output.stdout)
+                .trim_end_matches(['\r', '\n'])
+                .to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr)
+                .trim_end_matches(['\r', '\n'])
+                .to_string();
+            if !stdout.is_empty() {
+                trace!("STDOUT:\n{}", stdout);
+            }
+            if !stderr.is_empty() {
+                error!("STDERR:\n{}", stderr);
+            }
+
+            CommandOutput {
+                // A process terminated by a signal has no exit code
+                status: output.status.code().unwrap_or(1),
+                stdout,
+                stderr,
+            }
+        }
+        _ => {
+            RuntimeErrors::NotSupportedOS(None).exit();
+            CommandOutput { status: 1, stdout: String::new(), stderr: String::new() }
+        }
+    }
+}
+
+// Runs the program directly (not through the shell) with inherited stdio so interactive programs work.
+// The extra environment variables are set on top of the current environment.
+// Returns the exit code of the program.
+pub fn execute_interactive(cmd_array: Vec<String>, envs: Vec<(String, String)>) -> i32 {
+    if cmd_array.is_empty() {
+        RuntimeErrors::NoCommandsProvided.exit();
+    }
+
+    trace!("Executing command: {}", cmd_array.join(" "));
+
+    match init(cmd_array.clone()).as_str() {
+        "windows" => {
+            let (command, args) = cmd_array.split_first().unwrap();
+
+            let status = Command::new(command)
+                .args(args)
+                .envs(envs)
+                .status()
+                .expect("Failed to execute command");
+
+            // A process terminated by a signal has no exit code
+            status.code().unwrap_or(1)
+        }
+        _ => {
+            RuntimeErrors::NotSupportedOS(None).exit();
+            1
+        }
+    }
+}
+
 fn check_supported_os(settings: &Settings) -> String {
     let env = &settings.os;
 
@@ -0,0 +1,253 @@
+use serde_json::Value;
+use spdlog::prelude::*;
+use std::collections::HashSet;
+use std::fs::{ self, File };
+use std::io::{ self, Read, Write };
+use std::path::{ Path, PathBuf };
+
+// Compilation database (https://clang.llvm.org/docs/JSONCompilationDatabase.html)
+// CMake writes it to '<build_dir>/compile_commands.json' when CMAKE_EXPORT_COMPILE_COMMANDS is ON.
+// Only the Makefile and Ninja generators support it.
+pub const FILE_NAME: &str = "compile_commands.json";
+
+// How the database is exposed in the project root.
+pub enum ExportMode {
+    // Symlink to the build directory database, falls back to a copy
+    Link,
+    Copy,
+    None,
+}
+
+impl ExportMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "link" => Some(ExportMode::Link),
+            "copy" => Some(ExportMode::Copy),
+            "none" => Some(ExportMode::None),
+            _ => None,
+        }
+    }
+}
+
+// Exposes '<build_dir>/compile_commands.json' as '<project_dir>/compile_commands.json'.
+// If extra build directories are provided their databases are merged into a single file instead.
+// Returns the path of the exported database, or None if there was nothing to export.
+pub fn export(
+    build_dir: &Path,
+    extra_build_dirs: &[PathBuf],
+    project_dir: &Path,
+    mode: ExportMode
+) -> io::Result<Option<PathBuf>> {
+    if let ExportMode::None = mode {
+        return Ok(None);
+    }
+
+    let source = build_dir.join(FILE_NAME);
+    if !source.exists() {
+        return Ok(None);
+    }
+
+    // Never overwrite a database the user put in the project root
+    let destination = project_dir.join(FILE_NAME);
+    let mut build_dirs = vec![build_dir.to_path_buf()];
+    build_dirs.extend(extra_build_dirs.iter().cloned());
+    if fs::symlink_metadata(&destination).is_ok() && !is_exported_from(&destination, &build_dirs) {
+        warn!(
+            "{:?} was not exported by CPM, skipping the export. Remove it to export the compilation database.",
+            destination
+        );
+        return Ok(None);
+    }
+    remove_existing(&destination)?;
+
+    if !extra_build_dirs.is_empty() {
+        let mut databases = vec![source];
+        databases.extend(extra_build_dirs.iter().map(|dir| dir.join(FILE_NAME)));
+        merge(&databases, &destination)?;
+        return Ok(Some(destination));
+    }
+
+    match mode {
+        ExportMode::Link => {
+            if let Err(e) = symlink_file(&source, &destination) {
+                // Windows requires developer mode or elevated rights for symlinks
+                debug!("Failed to symlink {:?}, copying instead: {}", destination, e);
+                fs::copy(&source, &destination)?;
+            }
+        }
+        _ => {
+            fs::copy(&source, &destination)?;
+        }
+    }
+
+    Ok(Some(destination))
+}
+
+// True if the database in the project root was exported by 'build': a link, or a copy (possibly
+// merged) whose entries were all compiled inside the build directory.
+pub fn is_exported(path: &Path, build_dir: &Path) -> bool {
+    is_exported_from(path, &[build_dir.to_path_buf()])
+}
+
+// Same as is_exported, the entries may come from any of the (merged) build directories.
+fn is_exported_from(path: &Path, build_dirs: &[PathBuf]) -> bool {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => {
+            return true;
+        }
+        Ok(_) => {}
+        Err(_) => {
+            return false;
+        }
+    }
+
+    let entries = match fs::read_to_string(path).map(|contents| serde_json::from_str(&contents)) {
+        Ok(Ok(Value::Array(entries))) => entries,
+        _ => {
+            return false;
+        }
+    };
+    !entries.is_empty() &&
+        entries.iter().all(|entry| {
+            entry
+                .get("directory")
+                .and_then(|directory| directory.as_str())
+                .is_some_and(|directory| {
+                    build_dirs.iter().any(|build_dir| Path::new(directory).starts_with(build_dir))
+                })
+        })
+}
+
+// Concatenates the entries of multiple databases. The first database wins when several contain the same file.
+fn merge(databases: &[PathBuf], destination: &Path) -> io::Result<()> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut merged: Vec<Value> = Vec::new();
+
+    for database in databases {
+        if !database.exists() {
+            warn!("Compilation database not found, skipping: {:?}", database);
+            continue;
+        }
+
+        let mut contents = String::new();
+        File::open(database)?.read_to_string(&mut contents)?;
+        let entries: Vec<Value> = serde_json
+            ::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        for entry in entries {
+            let file = entry
+                .get("file")
+                .and_then(|file| file.as_str())
+                .unwrap_or_default()
+                .to_string();
+            if seen.insert(file) {
+                merged.push(entry);
+            }
+        }
+    }
+
+    let contents = serde_json::to_string_pretty(&merged)?;
+    let mut file = File::create(destination)?;
+    file.write_all(contents.as_bytes())
+}
+
+fn remove_existing(path: &Path) -> io::Result<()> {
+    // symlink_metadata so dangling symlinks are removed as well
+    match fs::symlink_metadata(path) {
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(windows)]
+fn symlink_file(source: &Path, destination: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(source, destination)
+}
+
+#[cfg(unix)]
+fn symlink_file(source: &Path, destination: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let name = format!("cpm-compile-commands-{}-{}", name, std::process::id());
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("Build")).unwrap();
+        fs::create_dir_all(dir.join("Other")).unwrap();
+        dir
+    }
+
+    fn write_database(dir: &Path, file: &str) {
+        let database = serde_json::json!([
+            {
+                "directory": dir.to_string_lossy(),
+                "command": "clang++ -c main.cpp",
+                "file": file,
+            }
+        ]);
+        fs::write(dir.join(FILE_NAME), database.to_string()).unwrap();
+    }
+
+    #[test]
+    fn replaces_an_exported_database() {
+        let root = temp_project("replace");
+        let build_dir = root.join("Build");
+        write_database(&build_dir, "main.cpp");
+        let destination = root.join(FILE_NAME);
+
+        let exported = export(&build_dir, &[], &root, ExportMode::Copy).unwrap();
+        assert_eq!(exported, Some(destination.clone()));
+        assert!(is_exported(&destination, &build_dir));
+
+        write_database(&build_dir, "other.cpp");
+        export(&build_dir, &[], &root, ExportMode::Copy).unwrap();
+        assert!(fs::read_to_string(&destination).unwrap().contains("other.cpp"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn keeps_a_database_not_exported_by_cpm() {
+        let root = temp_project("foreign");
+        let build_dir = root.join("Build");
+        write_database(&build_dir, "main.cpp");
+        let destination = root.join(FILE_NAME);
+        let hand_written = r#"[{"directory": "C:/elsewhere", "command": "cl", "file": "a.cpp"}]"#;
+        fs::write(&destination, hand_written).unwrap();
+
+        assert_eq!(export(&build_dir, &[], &root, ExportMode::Link).unwrap(), None);
+        assert_eq!(fs::read_to_string(&destination).unwrap(), hand_written);
+
+        fs::write(&destination, "not json").unwrap();
+        assert_eq!(export(&build_dir, &[], &root, ExportMode::Copy).unwrap(), None);
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "not json");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn refreshes_a_merged_database() {
+        let root = temp_project("merged");
+        let build_dir = root.join("Build");
+        let other_dir = root.join("Other");
+        write_database(&build_dir, "main.cpp");
+        write_database(&other_dir, "test.cpp");
+        let destination = root.join(FILE_NAME);
+        let extra_build_dirs = [other_dir.clone()];
+
+        export(&build_dir, &extra_build_dirs, &root, ExportMode::Link).unwrap();
+        let merged = fs::read_to_string(&destination).unwrap();
+        assert!(merged.contains("main.cpp") && merged.contains("test.cpp"));
+
+        write_database(&other_dir, "bench.cpp");
+        let exported = export(&build_dir, &extra_build_dirs, &root, ExportMode::Link).unwrap();
+        assert_eq!(exported, Some(destination.clone()));
+        assert!(fs::read_to_string(&destination).unwrap().contains("bench.cpp"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
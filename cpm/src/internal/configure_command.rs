@@ -0,0 +1,280 @@
+// Structured view of a cached CMake configure command line, i.e.
+//     cmake -S <source> -B <build> -G <generator> -DKEY[:TYPE]=VALUE -UKEY ...
+// Used to apply cache variable overrides on top of the last configure command.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub key: String,
+    // Optional cache entry type, i.e. BOOL, STRING, PATH, FILEPATH
+    pub value_type: Option<String>,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigureCommand {
+    pub program: String,
+    pub source_dir: Option<String>,
+    pub build_dir: Option<String>,
+    pub generator: Option<String>,
+    pub definitions: Vec<Definition>,
+    pub undefinitions: Vec<String>,
+    // Anything we do not understand is kept as is
+    pub other: Vec<String>,
+}
+
+impl ConfigureCommand {
+    pub fn parse(argv: &[String]) -> Self {
+        let mut command = ConfigureCommand::default();
+        let mut iter = argv.iter();
+
+        if let Some(program) = iter.next() {
+            command.program = program.clone();
+        }
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-S" => {
+                    command.source_dir = iter.next().cloned();
+                }
+                "-B" => {
+                    command.build_dir = iter.next().cloned();
+                }
+                "-G" => {
+                    command.generator = iter.next().cloned();
+                }
+                "-D" => {
+                    if let Some(definition) = iter.next().and_then(|d| Definition::parse(d)) {
+                        command.set_definition(definition);
+                    }
+                }
+                "-U" => {
+                    if let Some(key) = iter.next() {
+                        command.unset_definition(key);
+                    }
+                }
+                _ if arg.starts_with("-D") => {
+                    match Definition::parse(&arg[2..]) {
+                        Some(definition) => command.set_definition(definition),
+                        None => command.other.push(arg.clone()),
+                    }
+                }
+                _ if arg.starts_with("-U") => {
+                    command.unset_definition(&arg[2..]);
+                }
+                _ => command.other.push(arg.clone()),
+            }
+        }
+
+        command
+    }
+
+    pub fn to_argv(&self) -> Vec<String> {
+        let mut argv = vec![self.program.clone()];
+
+        if let Some(source_dir) = &self.source_dir {
+            argv.extend(["-S".to_string(), source_dir.clone()]);
+        }
+        if let Some(build_dir) = &self.build_dir {
+            argv.extend(["-B".to_string(), build_dir.clone()]);
+        }
+        if let Some(generator) = &self.generator {
+            argv.extend(["-G".to_string(), generator.clone()]);
+        }
+        argv.extend(self.other.iter().cloned());
+        for definition in &self.definitions {
+            argv.push(format!("-D{}", definition));
+        }
+        for key in &self.undefinitions {
+            argv.push(format!("-U{}", key));
+        }
+
+        argv
+    }
+
+    // Adds or replaces a definition, cancelling a previous undefine of the same key.
+    pub fn set_definition(&mut self, definition: Definition) {
+        self.undefinitions.retain(|key| *key != definition.key);
+        match self.definitions.iter_mut().find(|d| d.key == definition.key) {
+            Some(existing) => {
+                *existing = definition;
+            }
+            None => self.definitions.push(definition),
+        }
+    }
+
+    // Removes the definition and asks CMake to remove the entry from its cache as well.
+    pub fn unset_definition(&mut self, key: &str) {
+        self.definitions.retain(|d| d.key != key);
+        if !self.undefinitions.iter().any(|k| k == key) {
+            self.undefinitions.push(key.to_string());
+        }
+    }
+}
+
+impl Definition {
+    // Parses 'KEY=VALUE' or 'KEY:TYPE=VALUE'
+    pub fn parse(definition: &str) -> Option<Self> {
+        let (name, value) = definition.split_once('=')?;
+        let (key, value_type) = match name.split_once(':') {
+            Some((key, value_type)) => (key, Some(value_type.to_string())),
+            None => (name, None),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        Some(Definition {
+            key: key.to_string(),
+            value_type,
+            value: value.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Definition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value_type {
+            Some(value_type) => write!(f, "{}:{}={}", self.key, value_type, self.value),
+            None => write!(f, "{}={}", self.key, self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(arguments: &[&str]) -> Vec<String> {
+        arguments
+            .iter()
+            .map(|argument| argument.to_string())
+            .collect()
+    }
+
+    fn definition(key: &str, value_type: Option<&str>, value: &str) -> Definition {
+        Definition {
+            key: key.to_string(),
+            value_type: value_type.map(|value_type| value_type.to_string()),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_typed_and_untyped_definitions() {
+        let command = ConfigureCommand::parse(&argv(&[
+            "cmake",
+            "-DCMAKE_BUILD_TYPE:STRING=Debug",
+            "-D",
+            "BUILD_TESTS:BOOL=ON",
+            "-DVCPKG_ROOT=C:/vcpkg",
+            "-DFLAGS:STRING=-O2 -DNDEBUG=1",
+        ]));
+
+        assert_eq!(command.definitions, vec![
+            definition("CMAKE_BUILD_TYPE", Some("STRING"), "Debug"),
+            definition("BUILD_TESTS", Some("BOOL"), "ON"),
+            definition("VCPKG_ROOT", None, "C:/vcpkg"),
+            definition("FLAGS", Some("STRING"), "-O2 -DNDEBUG=1"),
+        ]);
+        assert!(command.other.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        assert_eq!(Definition::parse("NO_VALUE"), None);
+        assert_eq!(Definition::parse("=value"), None);
+        assert_eq!(Definition::parse(":BOOL=ON"), None);
+        assert_eq!(Definition::parse("EMPTY="), Some(definition("EMPTY", None, "")));
+
+        let command = ConfigureCommand::parse(&argv(&["cmake", "-DNO_VALUE", "-D", "=x"]));
+        assert!(command.definitions.is_empty());
+        assert_eq!(command.other, argv(&["-DNO_VALUE"]));
+    }
+
+    #[test]
+    fn parses_the_directories_and_generator() {
+        let command = ConfigureCommand::parse(&argv(&[
+            "cmake",
+            "-S",
+            "C:/project",
+            "-B",
+            "C:/project/Build",
+            "-G",
+            "Ninja Multi-Config",
+            "--fresh",
+        ]));
+
+        assert_eq!(command.program, "cmake");
+        assert_eq!(command.source_dir.as_deref(), Some("C:/project"));
+        assert_eq!(command.build_dir.as_deref(), Some("C:/project/Build"));
+        assert_eq!(command.generator.as_deref(), Some("Ninja Multi-Config"));
+        assert_eq!(command.other, argv(&["--fresh"]));
+    }
+
+    #[test]
+    fn undefine_removes_the_definition() {
+        let command = ConfigureCommand::parse(&argv(&[
+            "cmake",
+            "-DA=1",
+            "-DB:BOOL=ON",
+            "-UA",
+            "-U",
+            "C",
+            "-UA",
+        ]));
+
+        assert_eq!(command.definitions, vec![definition("B", Some("BOOL"), "ON")]);
+        assert_eq!(command.undefinitions, argv(&["A", "C"]));
+    }
+
+    #[test]
+    fn set_definition_replaces_in_place_and_cancels_the_undefine() {
+        let mut command = ConfigureCommand::parse(&argv(&[
+            "cmake",
+            "-DA=1",
+            "-DB:BOOL=ON",
+            "-DC=3",
+            "-UD",
+        ]));
+
+        command.set_definition(definition("B", Some("BOOL"), "OFF"));
+        command.set_definition(definition("D", None, "4"));
+
+        assert_eq!(command.definitions, vec![
+            definition("A", None, "1"),
+            definition("B", Some("BOOL"), "OFF"),
+            definition("C", None, "3"),
+            definition("D", None, "4"),
+        ]);
+        assert!(command.undefinitions.is_empty());
+
+        // A later definition on the command line wins as well
+        let command = ConfigureCommand::parse(&argv(&["cmake", "-DA=1", "-DB=2", "-DA:STRING=3"]));
+        assert_eq!(command.definitions, vec![
+            definition("A", Some("STRING"), "3"),
+            definition("B", None, "2"),
+        ]);
+    }
+
+    #[test]
+    fn to_argv_round_trips() {
+        let original = argv(&[
+            "cmake",
+            "-S",
+            ".",
+            "-B",
+            "Build",
+            "-G",
+            "Ninja",
+            "--log-level=VERBOSE",
+            "-DCMAKE_BUILD_TYPE:STRING=Release",
+            "-DBUILD_TESTS=ON",
+            "-UOLD_OPTION",
+        ]);
+        let command = ConfigureCommand::parse(&original);
+        assert_eq!(command.to_argv(), original);
+
+        let separate = argv(&["cmake", "-D", "A:BOOL=ON", "-U", "B"]);
+        let command = ConfigureCommand::parse(&separate);
+        assert_eq!(command.to_argv(), argv(&["cmake", "-DA:BOOL=ON", "-UB"]));
+    }
+}
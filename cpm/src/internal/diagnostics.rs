@@ -0,0 +1,380 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// Extracts compiler and linker diagnostics from build output. Supported formats:
+//     GCC/Clang:  src/main.cpp:12:5: warning: unused variable 'x' [-Wunused-variable]
+//                 src/main.cpp:12: error: expected ';'
+//     MSVC:       C:\project\src\main.cpp(12,5): warning C4101: 'x': unreferenced local variable [app.vcxproj]
+//                 main.obj : error LNK2019: unresolved external symbol ...
+//     CMake:      CMake Error at CMakeLists.txt:12 (add_executable):
+//                   Cannot find source file: main.cpp
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    // "warning" or "error"
+    pub severity: String,
+    // Compiler specific code, i.e. C4101, LNK2019 or -Wunused-variable
+    pub code: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct FileSummary {
+    pub warnings: usize,
+    pub errors: usize,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct DiagnosticSummary {
+    pub warnings: usize,
+    pub errors: usize,
+    pub files: BTreeMap<String, FileSummary>,
+    pub first_error: Option<Diagnostic>,
+}
+
+pub fn parse(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    let lines: Vec<&str> = output.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        let diagnostic = parse_cmake(line, &lines[index + 1..])
+            .or_else(|| parse_gnu(line))
+            .or_else(|| parse_msvc(line));
+        if let Some(diagnostic) = diagnostic {
+            // MSBuild repeats every diagnostic in its final summary
+            if !diagnostics.contains(&diagnostic) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    diagnostics
+}
+
+pub fn summarize(diagnostics: &[Diagnostic]) -> DiagnosticSummary {
+    let mut summary = DiagnosticSummary::default();
+
+    for diagnostic in diagnostics {
+        let file = summary.files.entry(diagnostic.file.clone()).or_default();
+        if diagnostic.severity == "error" {
+            file.errors += 1;
+            summary.errors += 1;
+            if summary.first_error.is_none() {
+                summary.first_error = Some(diagnostic.clone());
+            }
+        } else {
+            file.warnings += 1;
+            summary.warnings += 1;
+        }
+    }
+
+    summary
+}
+
+// CMake Error|Warning [(dev)] at <file>:<line> (<command>):
+//   <message>
+fn parse_cmake(line: &str, following: &[&str]) -> Option<Diagnostic> {
+    let (severity, rest) = if let Some(rest) = line.strip_prefix("CMake Error") {
+        ("error", rest)
+    } else if let Some(rest) = line.strip_prefix("CMake Warning") {
+        ("warning", rest)
+    } else {
+        return None;
+    };
+
+    // 'CMake Error: <message>' is on one line, otherwise the message is on the indented lines that follow
+    let message = match rest.strip_prefix(": ") {
+        Some(message) => message.to_string(),
+        None =>
+            following
+                .iter()
+                .map(|l| l.trim())
+                .find(|l| !l.is_empty())
+                .unwrap_or_default()
+                .to_string(),
+    };
+
+    let location = rest
+        .split_once(" at ")
+        .map(|(_, location)| location.trim_end_matches(':'));
+    let (file, line_number) = match location {
+        Some(location) => {
+            // Strip the trailing ' (<command>)'
+            let location = location.split(" (").next().unwrap_or(location);
+            match location.rsplit_once(':') {
+                Some((file, line_number)) => (file.to_string(), line_number.parse().ok()),
+                None => (location.to_string(), None),
+            }
+        }
+        None => ("CMake".to_string(), None),
+    };
+
+    Some(Diagnostic {
+        file,
+        line: line_number,
+        column: None,
+        severity: severity.to_string(),
+        code: None,
+        message,
+    })
+}
+
+// <file>:<line>[:<column>]: (warning|error|fatal error): <message> [<flag>]
+fn parse_gnu(line: &str) -> Option<Diagnostic> {
+    for (marker, severity) in [
+        (": fatal error: ", "error"),
+        (": error: ", "error"),
+        (": warning: ", "warning"),
+    ] {
+        let Some((location, message)) = line.split_once(marker) else {
+            continue;
+        };
+
+        // Windows paths contain a drive letter colon, so split the location from the right
+        let mut parts: Vec<&str> = location.rsplitn(3, ':').collect();
+        parts.reverse();
+        let (file, line_number, column) = match parts.as_slice() {
+            [file, line_number, column] if
+                line_number.parse::<u32>().is_ok() && column.parse::<u32>().is_ok()
+            => {
+                (file.to_string(), line_number.parse().ok(), column.parse().ok())
+            }
+            [.., line_number] if line_number.parse::<u32>().is_ok() => {
+                let file = location[..location.len() - line_number.len() - 1].to_string();
+                (file, line_number.parse().ok(), None)
+            }
+            _ => {
+                continue;
+            }
+        };
+        if line_number.is_none() {
+            continue;
+        }
+
+        // GCC/Clang append the warning flag: [-Wunused-variable]
+        let (message, code) = match message.rsplit_once(" [-W") {
+            Some((message, flag)) if flag.ends_with(']') => {
+                (message.to_string(), Some(format!("-W{}", flag.trim_end_matches(']'))))
+            }
+            _ => (message.to_string(), None),
+        };
+
+        return Some(Diagnostic {
+            file,
+            line: line_number,
+            column,
+            severity: severity.to_string(),
+            code,
+            message,
+        });
+    }
+    None
+}
+
+// <file>(<line>[,<column>]): (warning|error|fatal error) <code>: <message> [<project>]
+// <file> : (warning|error|fatal error) <code>: <message>
+fn parse_msvc(line: &str) -> Option<Diagnostic> {
+    for (marker, severity) in [
+        (": fatal error ", "error"),
+        (": error ", "error"),
+        (": warning ", "warning"),
+    ] {
+        let Some((location, rest)) = line.split_once(marker) else {
+            continue;
+        };
+        let Some((code, message)) = rest.split_once(": ") else {
+            continue;
+        };
+        // Codes are a letter prefix followed by digits, i.e. C4101, LNK2019, MSB8066
+        let valid_code =
+            code.chars().any(|c| c.is_ascii_digit()) &&
+            code.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid_code {
+            continue;
+        }
+
+        let location = location.trim();
+        let (file, line_number, column) = match location.strip_suffix(')') {
+            Some(without_paren) =>
+                match without_paren.rsplit_once('(') {
+                    Some((file, position)) => {
+                        let mut numbers = position.split(',').map(|n| n.trim().parse::<u32>().ok());
+                        let line_number = numbers.next().flatten();
+                        let column = numbers.next().flatten();
+                        (file.to_string(), line_number, column)
+                    }
+                    None => (location.to_string(), None, None),
+                }
+            None => (location.to_string(), None, None),
+        };
+
+        // MSBuild appends the project: [C:\project\Build\app.vcxproj]
+        let message = match message.rsplit_once(" [") {
+            Some((message, project)) if project.ends_with("proj]") => message.to_string(),
+            _ => message.to_string(),
+        };
+
+        return Some(Diagnostic {
+            file,
+            line: line_number,
+            column,
+            severity: severity.to_string(),
+            code: Some(code.to_string()),
+            message,
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(
+        file: &str,
+        line: Option<u32>,
+        column: Option<u32>,
+        severity: &str,
+        code: Option<&str>,
+        message: &str
+    ) -> Diagnostic {
+        Diagnostic {
+            file: file.to_string(),
+            line,
+            column,
+            severity: severity.to_string(),
+            code: code.map(|code| code.to_string()),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_gnu_diagnostics() {
+        let output = "\
+[1/2] Building CXX object CMakeFiles/app.dir/src/main.cpp.o
+src/main.cpp:12:5: warning: unused variable 'x' [-Wunused-variable]
+C:/project/src/util.cpp:3: error: expected ';'
+src/main.cpp:1:10: fatal error: missing.hpp: No such file or directory
+note: this line is not a diagnostic
+";
+        assert_eq!(parse(output), vec![
+            diagnostic(
+                "src/main.cpp",
+                Some(12),
+                Some(5),
+                "warning",
+                Some("-Wunused-variable"),
+                "unused variable 'x'"
+            ),
+            diagnostic("C:/project/src/util.cpp", Some(3), None, "error", None, "expected ';'"),
+            diagnostic(
+                "src/main.cpp",
+                Some(1),
+                Some(10),
+                "error",
+                None,
+                "missing.hpp: No such file or directory"
+            ),
+        ]);
+    }
+
+    #[test]
+    fn parses_msvc_diagnostics() {
+        let output = "\
+  C:\\project\\src\\main.cpp(12,5): warning C4101: 'x': unreferenced local variable [C:\\project\\Build\\app.vcxproj]
+main.obj : error LNK2019: unresolved external symbol \"void f()\"
+  C:\\project\\src\\main.cpp(12,5): warning C4101: 'x': unreferenced local variable [C:\\project\\Build\\app.vcxproj]
+Build succeeded with warnings: see the log
+";
+        assert_eq!(parse(output), vec![
+            diagnostic(
+                "C:\\project\\src\\main.cpp",
+                Some(12),
+                Some(5),
+                "warning",
+                Some("C4101"),
+                "'x': unreferenced local variable"
+            ),
+            diagnostic(
+                "main.obj",
+                None,
+                None,
+                "error",
+                Some("LNK2019"),
+                "unresolved external symbol \"void f()\""
+            ),
+        ]);
+    }
+
+    #[test]
+    fn parses_cmake_diagnostics() {
+        let output = "\
+CMake Error at src/CMakeLists.txt:12 (add_executable):
+  Cannot find source file:
+
+    main.cpp
+
+CMake Warning (dev) at CMakeLists.txt:3 (project):
+  Policy CMP0048 is not set
+CMake Error: The source directory does not exist.
+";
+        assert_eq!(parse(output), vec![
+            diagnostic(
+                "src/CMakeLists.txt",
+                Some(12),
+                None,
+                "error",
+                None,
+                "Cannot find source file:"
+            ),
+            diagnostic(
+                "CMakeLists.txt",
+                Some(3),
+                None,
+                "warning",
+                None,
+                "Policy CMP0048 is not set"
+            ),
+            diagnostic("CMake", None, None, "error", None, "The source directory does not exist."),
+        ]);
+    }
+
+    #[test]
+    fn parses_crlf_output() {
+        let output = "src/a.cpp:1:2: error: boom\r\nsrc/b.cpp(3): warning C4100: unused\r\n";
+        let diagnostics = parse(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message, "boom");
+        assert_eq!(diagnostics[1].message, "unused");
+        assert_eq!(diagnostics[1].line, Some(3));
+    }
+
+    #[test]
+    fn ignores_lines_that_only_look_like_diagnostics() {
+        let output = "\
+-- Looking for error: handler
+message: warning: no location
+ninja: error: loading 'build.ninja': not found
+";
+        assert!(parse(output).is_empty());
+    }
+
+    #[test]
+    fn summarizes_per_file() {
+        let diagnostics = vec![
+            diagnostic("a.cpp", Some(1), None, "warning", None, "first"),
+            diagnostic("b.cpp", Some(2), None, "error", None, "second"),
+            diagnostic("a.cpp", Some(3), None, "error", None, "third")
+        ];
+        let summary = summarize(&diagnostics);
+        assert_eq!(summary.warnings, 1);
+        assert_eq!(summary.errors, 2);
+        assert_eq!(summary.files["a.cpp"].warnings, 1);
+        assert_eq!(summary.files["a.cpp"].errors, 1);
+        assert_eq!(summary.files["b.cpp"].errors, 1);
+        assert_eq!(summary.first_error, Some(diagnostics[1].clone()));
+    }
+}
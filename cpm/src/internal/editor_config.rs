@@ -0,0 +1,61 @@
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+use crate::internal::install::Presets;
+
+// Editor configuration files written to the project root, embedded from 'presets/editor/' without
+// the leading dot:
+//     clang-format  ---> .clang-format
+//     clang-tidy    ---> .clang-tidy
+//     editorconfig  ---> .editorconfig
+pub const EDITOR_DIR: &str = "editor";
+pub const FILES: [&str; 3] = ["clang-format", "clang-tidy", "editorconfig"];
+
+// Resolves the names given on the command line, 'all' selects every file. Fails with the unknown name.
+pub fn resolve(names: &[String]) -> Result<Vec<&'static str>, String> {
+    let mut files: Vec<&'static str> = Vec::new();
+    for name in names {
+        let name = name.trim().trim_start_matches('.');
+        let selected: Vec<&'static str> = match name {
+            "all" => FILES.to_vec(),
+            _ => {
+                match FILES.iter().find(|file| **file == name) {
+                    Some(file) => vec![*file],
+                    None => {
+                        return Err(name.to_string());
+                    }
+                }
+            }
+        };
+        for file in selected {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+// Writes the files that do not exist yet, existing ones belong to the project.
+// Returns the written and the skipped files.
+pub fn write(project_root: &Path, files: &[&str]) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut skipped: Vec<PathBuf> = Vec::new();
+
+    for file in files {
+        let path = project_root.join(format!(".{}", file));
+        if path.exists() {
+            skipped.push(path);
+            continue;
+        }
+        let Some(embedded) = Presets::get(&format!("{}/{}", EDITOR_DIR, file)) else {
+            let message = format!("{} is not embedded", file);
+            return Err(io::Error::new(io::ErrorKind::NotFound, message));
+        };
+        fs::write(&path, embedded.data)?;
+        written.push(path);
+    }
+
+    Ok((written, skipped))
+}
@@ -0,0 +1,195 @@
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+use crate::internal::install::Presets;
+use crate::internal::template_engine::{ self, Variables };
+
+// Entrypoint scripts written to the project root, embedded from 'presets/entrypoints/':
+//     cpm      ---> POSIX sh (Git Bash, MSYS2, WSL, ...), marked executable
+//     cpm.ps1  ---> PowerShell
+//     cpm.bat  ---> cmd.exe
+// They call the CPM executable with '--no-init'. The executable path is relative to the project
+// root when the executable is inside the project (i.e. <project>/CPM/cpm.exe), so the project can be moved.
+pub const ENTRYPOINTS_DIR: &str = "entrypoints";
+pub const SCRIPTS: [&str; 3] = ["cpm", "cpm.ps1", "cpm.bat"];
+// Written in the header comment of every script
+const GENERATED_MARKER: &str = "generated by 'cpm init'";
+
+pub fn variables(project_root: &Path, exe_path: &Path) -> Variables {
+    let (relative, exe_path) = match exe_path.strip_prefix(project_root) {
+        Ok(relative) => (true, relative),
+        Err(_) => (false, exe_path),
+    };
+    let exe_path = exe_path.to_string_lossy().to_string();
+    let exe_name = Path::new(&exe_path)
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    let mut variables = Variables::new();
+    variables.insert("relative".to_string(), if relative { "ON" } else { "OFF" }.to_string());
+    variables.insert("exe_path_posix".to_string(), exe_path.replace('\\', "/"));
+    variables.insert("exe_path_windows".to_string(), exe_path.replace('/', "\\"));
+    // Single quoted PowerShell strings escape ' by doubling it
+    variables.insert(
+        "exe_path_powershell".to_string(),
+        exe_path.replace('/', "\\").replace('\'', "''")
+    );
+    variables.insert("exe_name".to_string(), exe_name);
+    variables
+}
+
+// Writes (overwrites) every entrypoint script. Returns the written files.
+pub fn write(project_root: &Path, exe_path: &Path) -> io::Result<Vec<PathBuf>> {
+    let variables = variables(project_root, exe_path);
+    let mut written: Vec<PathBuf> = Vec::new();
+
+    for script in SCRIPTS {
+        let path = project_root.join(script);
+        fs::write(&path, render(script, &variables)?)?;
+        set_executable(&path)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+// Entrypoint scripts that are missing or differ from what 'write' would write.
+pub fn outdated(project_root: &Path, exe_path: &Path) -> io::Result<Vec<PathBuf>> {
+    let variables = variables(project_root, exe_path);
+    let mut outdated: Vec<PathBuf> = Vec::new();
+
+    for script in SCRIPTS {
+        let path = project_root.join(script);
+        let current = fs::read_to_string(&path).ok();
+        if current.as_deref() != Some(render(script, &variables)?.as_str()) {
+            outdated.push(path);
+        }
+    }
+
+    Ok(outdated)
+}
+
+// Whether the file is an entrypoint written by CPM, so user scripts with the same name are left alone.
+pub fn is_generated(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|contents| contents.contains(GENERATED_MARKER))
+        .unwrap_or(false)
+}
+
+fn render(script: &str, variables: &Variables) -> io::Result<String> {
+    let Some(embedded) = Presets::get(&format!("{}/{}", ENTRYPOINTS_DIR, script)) else {
+        let message = format!("{} is not embedded", script);
+        return Err(io::Error::new(io::ErrorKind::NotFound, message));
+    };
+    let template = String::from_utf8_lossy(&embedded.data);
+    let mut contents = template_engine
+        ::render(&template, variables)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", script, e)))?;
+    // cmd.exe expects CRLF line endings, sh LF. The embedded files may have either (git autocrlf).
+    contents = contents.replace("\r\n", "\n");
+    if script.ends_with(".bat") {
+        contents = contents.replace('\n', "\r\n");
+    }
+    Ok(contents)
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if path.extension().is_none() {
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o755);
+        fs::set_permissions(path, permissions)?;
+    }
+    Ok(())
+}
+
+// Windows has no executable bit, the sh script is run through Git Bash/MSYS2 which do not need it
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let name = format!("cpm-entrypoint-{}-{}", name, std::process::id());
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn uses_a_relative_path_inside_the_project() {
+        let root = Path::new("C:/project");
+        let variables = variables(root, &root.join("CPM").join("cpm.exe"));
+        assert_eq!(variables["relative"], "ON");
+        assert_eq!(variables["exe_path_posix"], "CPM/cpm.exe");
+        assert_eq!(variables["exe_path_windows"], "CPM\\cpm.exe");
+        assert_eq!(variables["exe_name"], "cpm.exe");
+
+        let rendered = render("cpm", &variables).unwrap();
+        assert!(rendered.contains("CPM_EXE=\"$SCRIPT_DIR/CPM/cpm.exe\"\n"));
+    }
+
+    #[test]
+    fn uses_an_absolute_path_outside_the_project() {
+        let variables = variables(Path::new("C:/project"), Path::new("C:/tools/cpm/cpm.exe"));
+        assert_eq!(variables["relative"], "OFF");
+        assert_eq!(variables["exe_path_posix"], "C:/tools/cpm/cpm.exe");
+        assert_eq!(variables["exe_path_windows"], "C:\\tools\\cpm\\cpm.exe");
+
+        let rendered = render("cpm", &variables).unwrap();
+        assert!(rendered.contains("CPM_EXE=\"C:/tools/cpm/cpm.exe\"\n"));
+        assert!(!rendered.contains("{{"));
+    }
+
+    #[test]
+    fn escapes_single_quotes_for_powershell() {
+        let variables = variables(Path::new("C:/project"), Path::new("C:/O'Brien/cpm.exe"));
+        assert_eq!(variables["exe_path_powershell"], "C:\\O''Brien\\cpm.exe");
+
+        let rendered = render("cpm.ps1", &variables).unwrap();
+        assert!(rendered.contains("$CpmExe = 'C:\\O''Brien\\cpm.exe'"));
+    }
+
+    #[test]
+    fn writes_the_batch_script_with_crlf_only() {
+        let variables = variables(Path::new("C:/project"), Path::new("C:/project/CPM/cpm.exe"));
+        let rendered = render("cpm.bat", &variables).unwrap();
+        assert!(rendered.contains("\r\n"));
+        assert_eq!(rendered.matches('\n').count(), rendered.matches("\r\n").count());
+        assert!(!rendered.contains("\r\r"));
+
+        for script in ["cpm", "cpm.ps1"] {
+            assert!(!render(script, &variables).unwrap().contains('\r'), "{}", script);
+        }
+    }
+
+    #[test]
+    fn nothing_is_outdated_after_writing() {
+        let root = temp_project("outdated");
+        let exe_path = root.join("CPM").join("cpm.exe");
+        assert_eq!(outdated(&root, &exe_path).unwrap().len(), SCRIPTS.len());
+
+        let written = write(&root, &exe_path).unwrap();
+        assert_eq!(written.len(), SCRIPTS.len());
+        assert!(outdated(&root, &exe_path).unwrap().is_empty());
+        assert!(written.iter().all(|path| is_generated(path)));
+
+        // A moved executable changes the scripts
+        let moved = root.join("tools").join("cpm.exe");
+        assert_eq!(outdated(&root, &moved).unwrap().len(), SCRIPTS.len());
+
+        fs::write(root.join("cpm"), "#!/bin/sh\necho user script\n").unwrap();
+        assert!(!is_generated(&root.join("cpm")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+use serde::Deserialize;
+use std::fs::{ self, File };
+use std::io::{ self, Read };
+use std::path::{ Path, PathBuf };
+
+// CMake File API (https://cmake.org/cmake/help/latest/manual/cmake-file-api.7.html)
+// A query file is written into '<build_dir>/.cmake/api/v1/query/client-cpm/' before configuring.
+// CMake then writes the reply into '<build_dir>/.cmake/api/v1/reply/' which we parse here.
+const CLIENT_NAME: &str = "client-cpm";
+const CODEMODEL_QUERY: &str = "codemodel-v2";
+
+#[derive(Debug, Clone)]
+pub struct CodeModel {
+    pub build_dir: PathBuf,
+    pub source_dir: PathBuf,
+    pub configurations: Vec<Configuration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Configuration {
+    // Empty for single-config generators without CMAKE_BUILD_TYPE set.
+    pub name: String,
+    pub targets: Vec<Target>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub name: String,
+    // EXECUTABLE, STATIC_LIBRARY, SHARED_LIBRARY, MODULE_LIBRARY, OBJECT_LIBRARY, INTERFACE_LIBRARY or UTILITY
+    pub target_type: String,
+    // Relative to the build directory unless CMake reports an absolute path.
+    pub artifacts: Vec<String>,
+    // Relative to the source directory unless CMake reports an absolute path.
+    pub sources: Vec<String>,
+    // Names of the targets this target depends on.
+    pub dependencies: Vec<String>,
+}
+
+// Raw reply objects. Only the fields we use are deserialized.
+
+#[derive(Deserialize, Debug)]
+struct ReplyIndex {
+    reply: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ReplyFileReference {
+    #[serde(rename = "jsonFile")]
+    json_file: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawCodeModel {
+    paths: RawPaths,
+    configurations: Vec<RawConfiguration>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawPaths {
+    source: String,
+    build: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawConfiguration {
+    name: String,
+    #[serde(default)]
+    targets: Vec<RawTargetReference>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawTargetReference {
+    #[serde(rename = "jsonFile")]
+    json_file: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawTarget {
+    name: String,
+    id: String,
+    #[serde(rename = "type")]
+    target_type: String,
+    #[serde(default)]
+    artifacts: Vec<RawPath>,
+    #[serde(default)]
+    sources: Vec<RawPath>,
+    #[serde(default)]
+    dependencies: Vec<RawDependency>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawPath {
+    path: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawDependency {
+    id: String,
+}
+
+pub fn query_dir(build_dir: &Path) -> PathBuf {
+    build_dir.join(".cmake").join("api").join("v1").join("query").join(CLIENT_NAME)
+}
+
+pub fn reply_dir(build_dir: &Path) -> PathBuf {
+    build_dir.join(".cmake").join("api").join("v1").join("reply")
+}
+
+// Writes the (empty) codemodel query file. CMake picks it up on the next configure.
+pub fn write_query(build_dir: &Path) -> io::Result<()> {
+    let dir = query_dir(build_dir);
+    fs::create_dir_all(&dir)?;
+    File::create(dir.join(CODEMODEL_QUERY))?;
+    Ok(())
+}
+
+// Parses the latest codemodel reply from the build directory.
+pub fn load_codemodel(build_dir: &Path) -> io::Result<CodeModel> {
+    let reply_dir = reply_dir(build_dir);
+    let index_path = latest_index(&reply_dir)?;
+    let index: ReplyIndex = read_json(&index_path)?;
+
+    let client = index.reply
+        .get(CLIENT_NAME)
+        .ok_or_else(|| not_found(format!("No '{}' reply in {:?}", CLIENT_NAME, index_path)))?;
+    let codemodel_ref = client
+        .get(CODEMODEL_QUERY)
+        .ok_or_else(|| not_found(format!("No '{}' reply in {:?}", CODEMODEL_QUERY, index_path)))?;
+    let codemodel_ref: ReplyFileReference = serde_json
+        ::from_value(codemodel_ref.clone())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let raw: RawCodeModel = read_json(&reply_dir.join(&codemodel_ref.json_file))?;
+
+    let mut configurations = Vec::new();
+    for raw_config in raw.configurations {
+        let mut raw_targets = Vec::new();
+        for target_ref in &raw_config.targets {
+            let raw_target: RawTarget = read_json(&reply_dir.join(&target_ref.json_file))?;
+            raw_targets.push(raw_target);
+        }
+
+        // Dependencies are reported by id, resolve them to names within the same configuration.
+        let id_to_name: std::collections::HashMap<String, String> = raw_targets
+            .iter()
+            .map(|t| (t.id.clone(), t.name.clone()))
+            .collect();
+
+        let targets = raw_targets
+            .into_iter()
+            .map(|t| Target {
+                dependencies: t.dependencies
+                    .iter()
+                    .map(|d| id_to_name.get(&d.id).cloned().unwrap_or_else(|| d.id.clone()))
+                    .collect(),
+                name: t.name,
+                target_type: t.target_type,
+                artifacts: t.artifacts
+                    .into_iter()
+                    .map(|a| a.path)
+                    .collect(),
+                sources: t.sources
+                    .into_iter()
+                    .map(|s| s.path)
+                    .collect(),
+            })
+            .collect();
+
+        configurations.push(Configuration { name: raw_config.name, targets });
+    }
+
+    Ok(CodeModel {
+        build_dir: PathBuf::from(raw.paths.build),
+        source_dir: PathBuf::from(raw.paths.source),
+        configurations,
+    })
+}
+
+impl CodeModel {
+    // Multi-config generators (Visual Studio) report one configuration per build type.
+    // Single-config generators (Ninja) report only the configured one, so fall back to it.
+    pub fn configuration(&self, build_type: &str) -> Option<&Configuration> {
+        self.configurations
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(build_type))
+            .or_else(|| {
+                if self.configurations.len() == 1 { self.configurations.first() } else { None }
+            })
+    }
+
+    // Resolves a path reported by CMake against the build directory.
+    pub fn artifact_path(&self, artifact: &str) -> PathBuf {
+        let path = Path::new(artifact);
+        if path.is_absolute() { path.to_path_buf() } else { self.build_dir.join(path) }
+    }
+}
+
+impl Configuration {
+    pub fn target(&self, name: &str) -> Option<&Target> {
+        self.targets.iter().find(|t| t.name == name)
+    }
+}
+
+impl Target {
+    pub fn is_executable(&self) -> bool {
+        self.target_type == "EXECUTABLE"
+    }
+
+    pub fn is_shared_library(&self) -> bool {
+        self.target_type == "SHARED_LIBRARY" || self.target_type == "MODULE_LIBRARY"
+    }
+}
+
+// Reply index files are named 'index-<timestamp>.json', the lexicographically last one is the newest.
+fn latest_index(reply_dir: &Path) -> io::Result<PathBuf> {
+    let mut indices: Vec<PathBuf> = fs
+        ::read_dir(reply_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("index-") && name.ends_with(".json"))
+                .unwrap_or(false)
+        })
+        .collect();
+    indices.sort();
+    indices.pop().ok_or_else(|| not_found(format!("No reply index found in {:?}", reply_dir)))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let mut file = File::open(path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn not_found(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, message)
+}
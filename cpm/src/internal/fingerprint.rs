@@ -0,0 +1,265 @@
+use sha2::{ Digest, Sha256 };
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{ self, Read };
+use std::path::Path;
+use walkdir::WalkDir;
+
+// Fingerprint of everything that affects the result of a CMake configure:
+// - the configure command line
+// - the build type, single-config generators bake it into the build tree
+// - the toolchain file
+// - compiler related environment variables
+// - the CMake sources of the project (CMakeLists.txt, *.cmake, presets, vcpkg manifest)
+// Each input is stored as a separate entry so the reason for a reconfigure can be reported.
+pub type Fingerprint = BTreeMap<String, String>;
+
+const ENVIRONMENT_VARIABLES: [&str; 5] = ["CC", "CXX", "CFLAGS", "CXXFLAGS", "LDFLAGS"];
+const ENVIRONMENT_PREFIXES: [&str; 2] = ["CMAKE_", "VCPKG_"];
+const CMAKE_FILE_NAMES: [&str; 4] = [
+    "CMakeLists.txt",
+    "CMakePresets.json",
+    "CMakeUserPresets.json",
+    "vcpkg.json",
+];
+
+pub fn compute(
+    configure_command: &[String],
+    build_type: &str,
+    toolchain_file: &str,
+    source_dir: &Path,
+    excluded_dirs: &[&Path]
+) -> Fingerprint {
+    let mut fingerprint = Fingerprint::new();
+
+    fingerprint.insert("argv".to_string(), configure_command.join("\u{1f}"));
+    fingerprint.insert("build_type".to_string(), build_type.to_string());
+
+    if !toolchain_file.is_empty() {
+        fingerprint.insert(
+            "toolchain".to_string(),
+            hash_file(Path::new(toolchain_file)).unwrap_or_else(|_| "missing".to_string())
+        );
+    }
+
+    for (key, value) in std::env::vars() {
+        let relevant =
+            ENVIRONMENT_VARIABLES.contains(&key.as_str()) ||
+            ENVIRONMENT_PREFIXES.iter().any(|prefix| key.starts_with(prefix));
+        if relevant {
+            fingerprint.insert(format!("env:{}", key), value);
+        }
+    }
+
+    let walker = WalkDir::new(source_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy();
+            // Skip generated directories, other build trees and VCS metadata
+            !(
+                excluded_dirs.iter().any(|excluded| path.starts_with(excluded)) ||
+                name == ".git" ||
+                (entry.file_type().is_dir() && path.join("CMakeCache.txt").exists())
+            )
+        });
+    for entry in walker.filter_map(|entry| entry.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy();
+        if CMAKE_FILE_NAMES.contains(&name.as_ref()) || name.ends_with(".cmake") {
+            let relative = entry.path().strip_prefix(source_dir).unwrap_or(entry.path());
+            if let Ok(hash) = hash_file(entry.path()) {
+                fingerprint.insert(format!("file:{}", relative.to_string_lossy()), hash);
+            }
+        }
+    }
+
+    fingerprint
+}
+
+// Describes the first difference between two fingerprints, None if they are equal.
+pub fn describe_change(previous: &Fingerprint, current: &Fingerprint) -> Option<String> {
+    if previous.is_empty() {
+        return Some("no previous configure recorded".to_string());
+    }
+
+    for (key, value) in current {
+        match previous.get(key) {
+            Some(previous_value) if previous_value == value => {}
+            Some(_) => {
+                return Some(format!("{} changed", describe_key(key)));
+            }
+            None => {
+                return Some(format!("{} added", describe_key(key)));
+            }
+        }
+    }
+    previous
+        .keys()
+        .find(|key| !current.contains_key(*key))
+        .map(|key| format!("{} removed", describe_key(key)))
+}
+
+fn describe_key(key: &str) -> String {
+    if key == "argv" {
+        "configure command".to_string()
+    } else if key == "build_type" {
+        "build type".to_string()
+    } else if key == "toolchain" {
+        "toolchain file".to_string()
+    } else if let Some(variable) = key.strip_prefix("env:") {
+        format!("environment variable '{}'", variable)
+    } else if let Some(file) = key.strip_prefix("file:") {
+        format!("'{}'", file)
+    } else {
+        key.to_string()
+    }
+}
+
+// SHA-256 of the file contents as a lowercase hex string.
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let name = format!("cpm-fingerprint-{}-{}", name, std::process::id());
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("cmake")).unwrap();
+        fs::create_dir_all(dir.join("Build")).unwrap();
+        fs::write(dir.join("CMakeLists.txt"), "project(demo)\n").unwrap();
+        fs::write(dir.join("cmake").join("deps.cmake"), "# deps\n").unwrap();
+        fs::write(dir.join("main.cpp"), "int main() {}\n").unwrap();
+        dir
+    }
+
+    fn argv(arguments: &[&str]) -> Vec<String> {
+        arguments
+            .iter()
+            .map(|argument| argument.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn changes_with_the_build_type() {
+        let root = temp_project("build-type");
+        let command = argv(&["cmake", "-S", ".", "-B", "Build", "-G", "Ninja"]);
+        let debug = compute(&command, "Debug", "", &root, &[]);
+        let release = compute(&command, "Release", "", &root, &[]);
+        assert_ne!(debug, release);
+        assert_eq!(describe_change(&debug, &release), Some("build type changed".to_string()));
+        assert_eq!(describe_change(&debug, &compute(&command, "Debug", "", &root, &[])), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn changes_with_the_command_and_the_cmake_sources() {
+        let root = temp_project("sources");
+        let command = argv(&["cmake", "-DA=1"]);
+        let previous = compute(&command, "Debug", "", &root, &[]);
+        assert!(previous.contains_key("file:CMakeLists.txt"));
+        assert!(previous.keys().any(|key| key.ends_with("deps.cmake")));
+        assert!(!previous.keys().any(|key| key.ends_with("main.cpp")));
+
+        let changed = compute(&argv(&["cmake", "-DA=2"]), "Debug", "", &root, &[]);
+        let change = describe_change(&previous, &changed);
+        assert_eq!(change, Some("configure command changed".to_string()));
+
+        // Other sources do not matter
+        fs::write(root.join("main.cpp"), "int main() { return 1; }\n").unwrap();
+        assert_eq!(describe_change(&previous, &compute(&command, "Debug", "", &root, &[])), None);
+
+        fs::write(root.join("CMakeLists.txt"), "project(demo CXX)\n").unwrap();
+        let current = compute(&command, "Debug", "", &root, &[]);
+        let change = describe_change(&previous, &current);
+        assert_eq!(change, Some("'CMakeLists.txt' changed".to_string()));
+
+        fs::remove_file(root.join("cmake").join("deps.cmake")).unwrap();
+        let removed = compute(&command, "Debug", "", &root, &[]);
+        assert!(describe_change(&current, &removed).unwrap().ends_with("deps.cmake' removed"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn skips_excluded_directories_and_build_trees() {
+        let root = temp_project("excluded");
+        let build_dir = root.join("Build");
+        fs::write(build_dir.join("generated.cmake"), "").unwrap();
+        let other_build = root.join("out");
+        fs::create_dir_all(&other_build).unwrap();
+        fs::write(other_build.join("CMakeCache.txt"), "").unwrap();
+        fs::write(other_build.join("CMakeLists.txt"), "").unwrap();
+
+        let fingerprint = compute(&[], "Debug", "", &root, &[&build_dir]);
+        let files: Vec<&String> = fingerprint
+            .keys()
+            .filter(|key| key.starts_with("file:"))
+            .collect();
+        assert_eq!(files.len(), 2, "{:?}", files);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn tracks_the_toolchain_file() {
+        let root = temp_project("toolchain");
+        let toolchain = root.join("toolchain.txt");
+        let toolchain_file = toolchain.to_string_lossy().to_string();
+        let missing = compute(&[], "Debug", &toolchain_file, &root, &[]);
+        assert_eq!(missing["toolchain"], "missing");
+
+        fs::write(&toolchain, "abc").unwrap();
+        let present = compute(&[], "Debug", &toolchain_file, &root, &[]);
+        assert_eq!(
+            present["toolchain"],
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let change = describe_change(&missing, &present);
+        assert_eq!(change, Some("toolchain file changed".to_string()));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn describes_the_first_difference() {
+        let fingerprint = |entries: &[(&str, &str)]| -> Fingerprint {
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+        let previous = fingerprint(&[("argv", "cmake"), ("env:CC", "clang")]);
+        let change = describe_change(&Fingerprint::new(), &previous);
+        assert_eq!(change, Some("no previous configure recorded".to_string()));
+
+        let current = fingerprint(&[("argv", "cmake"), ("env:CXX", "clang++")]);
+        let change = describe_change(&previous, &current);
+        assert_eq!(change, Some("environment variable 'CXX' added".to_string()));
+
+        let current = fingerprint(&[("argv", "cmake")]);
+        let change = describe_change(&previous, &current);
+        assert_eq!(change, Some("environment variable 'CC' removed".to_string()));
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+use crate::internal::{ compile_commands, entrypoint, variant };
+use crate::internal::settings::Settings;
+
+// Paths generated by CPM are kept out of version control by '.gitignore' at the project root:
+//     # Generated by CPM
+//     /Build/
+//     /cpm.bat
+// Entries are compared without the leading '/', and a directory entry is also covered without the
+// trailing one, so 'Build', 'Build/' and '/Build/' are the same entry and never added twice.
+// A '!' entry re-includes a path matched by an earlier entry, so the block keeps the entry order.
+// Missing entries are added to the CPM block (created at the end).
+pub const FILE_NAME: &str = ".gitignore";
+const HEADER: &str = "# Generated by CPM";
+
+pub fn file_path(project_root: &Path) -> PathBuf {
+    project_root.join(FILE_NAME)
+}
+
+// Entries for the generated paths inside the project, anchored to the project root.
+pub fn entries(settings: &Settings, project_root: &Path) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+    let relative = |path: &Path| {
+        path.strip_prefix(project_root)
+            .ok()
+            .filter(|relative| !relative.as_os_str().is_empty())
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+    };
+
+    // With a variant selected the build directory is '<Build>/<variant>', the whole tree is ignored
+    let build_dir = variant::base_build_dir(&settings.build_dir, &settings.build_variant);
+    let install_dir = Path::new(&settings.install_dir);
+    let dist_dir = Path::new(&settings.dist_dir);
+    for dir in [build_dir.as_path(), install_dir, dist_dir] {
+        if let Some(dir) = relative(dir) {
+            entries.push(format!("/{}/", dir));
+        }
+    }
+    for script in entrypoint::SCRIPTS {
+        entries.push(format!("/{}", script));
+    }
+    // '/cpm' also matches the '<project>/CPM/' executable directory when git ignores case
+    // (the default on Windows), so that directory is included again
+    if let Some(exe_dir) = relative(Path::new(&settings.exe_dir)) {
+        let collides = entrypoint::SCRIPTS
+            .iter()
+            .any(|script| script.eq_ignore_ascii_case(&exe_dir));
+        if collides {
+            entries.push(format!("!/{}/", exe_dir));
+        }
+    }
+    // Exported by 'build', a link or a copy of the build directory's database
+    entries.push(format!("/{}", compile_commands::FILE_NAME));
+    // The settings file holds absolute paths of this machine, i.e. with the executable in '<project>/CPM/'
+    let settings_file = Settings::get_settings_path().ok().and_then(|path| relative(&path));
+    if let Some(settings_file) = settings_file {
+        entries.push(format!("/{}", settings_file));
+    }
+
+    entries
+}
+
+// Entries that are not listed yet.
+pub fn missing(contents: &str, entries: &[String]) -> Vec<String> {
+    let listed: Vec<&str> = contents.lines().map(normalize).collect();
+    entries
+        .iter()
+        .filter(|entry| {
+            // 'Build' also ignores the directory, but 'cpm/' does not ignore the 'cpm' script
+            let entry = normalize(entry);
+            let covered = listed.contains(&entry) || listed.contains(&entry.trim_end_matches('/'));
+            !covered
+        })
+        .cloned()
+        .collect()
+}
+
+// Adds the missing entries. Returns None if every entry is already listed.
+pub fn update(contents: &str, entries: &[String]) -> Option<String> {
+    let missing = missing(contents, entries);
+    if missing.is_empty() {
+        return None;
+    }
+
+    let mut lines: Vec<String> = contents
+        .lines()
+        .map(|line| line.to_string())
+        .collect();
+    match lines.iter().position(|line| line.trim() == HEADER) {
+        Some(header) => {
+            // The block ends at the first empty line
+            let end = lines[header + 1..]
+                .iter()
+                .position(|line| line.trim().is_empty())
+                .map(|i| header + 1 + i)
+                .unwrap_or(lines.len());
+            // New entries go before the re-included paths of the block, so they stay included
+            let (negated, plain): (Vec<String>, Vec<String>) = missing
+                .into_iter()
+                .partition(|entry| entry.starts_with('!'));
+            let first_negated = lines[header + 1..end]
+                .iter()
+                .position(|line| line.trim().starts_with('!'))
+                .map(|i| header + 1 + i)
+                .unwrap_or(end);
+            let added = plain.len();
+            lines.splice(first_negated..first_negated, plain);
+            lines.splice(end + added..end + added, negated);
+        }
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(HEADER.to_string());
+            lines.extend(missing);
+        }
+    }
+
+    let line_ending = if contents.contains("\r\n") { "\r\n" } else { "\n" };
+    Some(format!("{}{}", lines.join(line_ending), line_ending))
+}
+
+// Creates or updates the file. Returns the added entries.
+pub fn write(project_root: &Path, entries: &[String]) -> io::Result<Vec<String>> {
+    let path = file_path(project_root);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    let missing = missing(&contents, entries);
+    if let Some(updated) = update(&contents, entries) {
+        fs::write(&path, updated)?;
+    }
+    Ok(missing)
+}
+
+fn normalize(entry: &str) -> &str {
+    entry.trim().trim_start_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn treats_directory_spellings_as_one_entry() {
+        let wanted = list(&["/Build/", "/Install/", "/cpm"]);
+        for listed in ["Build", "Build/", "/Build/", "  /Build/  "] {
+            let contents = format!("{}\nInstall/\n/cpm\n", listed);
+            assert!(missing(&contents, &wanted).is_empty(), "{}", listed);
+            assert_eq!(update(&contents, &wanted), None);
+        }
+
+        // A directory entry does not ignore the script of the same name
+        assert_eq!(missing("cpm/\n", &list(&["/cpm"])), list(&["/cpm"]));
+    }
+
+    #[test]
+    fn creates_the_block_at_the_end() {
+        let wanted = list(&["/Build/", "/cpm"]);
+        assert_eq!(update("", &wanted).unwrap(), "# Generated by CPM\n/Build/\n/cpm\n");
+        assert_eq!(
+            update("*.log\n/cpm", &wanted).unwrap(),
+            "*.log\n/cpm\n\n# Generated by CPM\n/Build/\n"
+        );
+    }
+
+    #[test]
+    fn extends_the_existing_block() {
+        let contents = "*.log\n\n# Generated by CPM\n/Build/\n\n# Editors\n.vs/\n";
+        let updated = update(contents, &list(&["/Build/", "/cpm", "/cpm.bat"])).unwrap();
+        assert_eq!(
+            updated,
+            "*.log\n\n# Generated by CPM\n/Build/\n/cpm\n/cpm.bat\n\n# Editors\n.vs/\n"
+        );
+        assert_eq!(updated.matches(HEADER).count(), 1);
+        assert_eq!(update(&updated, &list(&["/cpm.bat"])), None);
+    }
+
+    #[test]
+    fn keeps_the_re_include_after_the_ignored_entries() {
+        let wanted = list(&["/Build/", "/cpm", "!/CPM/", "/compile_commands.json"]);
+        let updated = update("", &wanted).unwrap();
+        assert_eq!(
+            updated,
+            "# Generated by CPM\n/Build/\n/cpm\n!/CPM/\n/compile_commands.json\n"
+        );
+
+        // Entries missing from an older block are added before its re-includes
+        let contents = "# Generated by CPM\n/Build/\n!/CPM/\n";
+        let updated = update(contents, &wanted).unwrap();
+        assert_eq!(
+            updated,
+            "# Generated by CPM\n/Build/\n/cpm\n/compile_commands.json\n!/CPM/\n"
+        );
+
+        let contents = "# Generated by CPM\n/Build/\n/cpm\n";
+        let updated = update(contents, &wanted).unwrap();
+        assert_eq!(
+            updated,
+            "# Generated by CPM\n/Build/\n/cpm\n/compile_commands.json\n!/CPM/\n"
+        );
+    }
+
+    #[test]
+    fn preserves_crlf_line_endings() {
+        let contents = "*.log\r\n\r\n# Generated by CPM\r\n/Build/\r\n";
+        let updated = update(contents, &list(&["/Build/", "/cpm"])).unwrap();
+        assert_eq!(updated, "*.log\r\n\r\n# Generated by CPM\r\n/Build/\r\n/cpm\r\n");
+        assert_eq!(updated.matches('\n').count(), updated.matches("\r\n").count());
+    }
+}
@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use crate::internal::configure_command::Definition;
+
+// A CMake configuration of the project for an IDE (Visual Studio, CLion), one per system type and
+// build type. IDE entries are named 'cpm-<id>' so they can be told apart from the user's own ones,
+// i.e. 'cpm-msvc-Debug'. Each profile has its own build tree next to CPM's and is generated with
+// Ninja, the IDEs set up the MSVC environment themselves.
+pub const PREFIX: &str = "cpm-";
+pub const GENERATOR: &str = "Ninja";
+pub const BUILD_TYPES: [&str; 2] = ["Debug", "Release"];
+
+#[derive(Debug, Clone)]
+pub struct CMakeProfile {
+    // i.e. 'msvc-Debug'
+    pub id: String,
+    pub system_type: String,
+    pub build_type: String,
+    pub generator: String,
+    // Without CMAKE_BUILD_TYPE, the IDE sets it from the build type
+    pub definitions: Vec<Definition>,
+    pub build_dir: PathBuf,
+    pub install_dir: PathBuf,
+}
+
+impl CMakeProfile {
+    pub fn name(&self) -> String {
+        format!("{}{}", PREFIX, self.id)
+    }
+
+    pub fn is_msvc(&self) -> bool {
+        self.system_type == "nt/msvc"
+    }
+}
+
+// 'nt/msvc' -> 'msvc', 'unix/clang' -> 'clang', ...
+pub fn compiler_name(system_type: &str) -> &str {
+    system_type.rsplit('/').next().unwrap_or(system_type)
+}
+
+pub fn is_generated(name: &str) -> bool {
+    name.starts_with(PREFIX)
+}
@@ -1,5 +1,11 @@
 use rust_embed::RustEmbed;
 use serde::{ Deserialize, Serialize };
+use serde_json::ser::PrettyFormatter;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::internal::json_edit;
 
 #[derive(RustEmbed)]
 #[folder = "presets/"]
@@ -8,6 +14,9 @@ pub struct Presets;
 pub struct Config {
     pub os_target: String,
     pub config: MultiOSConfig,
+    // Runtime asset directories (relative to the project root) staged next to the executables
+    #[serde(default)]
+    pub assets: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -43,3 +52,71 @@ pub struct Package {
     pub library: String,
     pub triplet: String,
 }
+
+pub fn retrieve_install(file_path: &Path) -> Result<Config, Box<dyn std::error::Error>> {
+    // Open the file
+    let mut file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+
+    // Read the contents into a string
+    let mut config_data = String::new();
+    file.read_to_string(&mut config_data).map_err(|e| format!("Failed to read file data: {}", e))?;
+
+    // Deserialize the JSON string into Config struct
+    let config: Config = serde_json
+        ::from_str(&config_data)
+        .map_err(|e| format!("Failed to parse JSON data: {}", e))?;
+
+    // Return the deserialized config
+    Ok(config)
+}
+
+// Location of the package list in the install config
+const PACKAGES_PATH: [&str; 3] = ["config", "windows", "packages"];
+
+// Adds the package to the install config text, keeping its formatting.
+// Returns None if a package with the same library is already listed.
+pub fn add_package(contents: &str, package: &Package) -> Result<Option<String>, String> {
+    let Some(array) = json_edit::find(contents, &PACKAGES_PATH)? else {
+        return Err("no 'config.windows.packages' list".to_string());
+    };
+    if find_package_index(contents, &package.library)?.is_some() {
+        return Ok(None);
+    }
+
+    let indent_unit = json_edit::indent_unit(contents);
+    let mut value = Vec::new();
+    let formatter = PrettyFormatter::with_indent(indent_unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut value, formatter);
+    package.serialize(&mut serializer).map_err(|e| e.to_string())?;
+    let value = String::from_utf8(value).map_err(|e| e.to_string())?;
+
+    json_edit::append_element(contents, array, &value).map(Some)
+}
+
+// Removes the package from the install config text, keeping its formatting.
+// Returns None if the package is not listed.
+pub fn remove_package(contents: &str, library: &str) -> Result<Option<String>, String> {
+    let Some(array) = json_edit::find(contents, &PACKAGES_PATH)? else {
+        return Err("no 'config.windows.packages' list".to_string());
+    };
+    match find_package_index(contents, library)? {
+        Some(index) => json_edit::remove_element(contents, array, index).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn find_package_index(contents: &str, library: &str) -> Result<Option<usize>, String> {
+    let Some(array) = json_edit::find(contents, &PACKAGES_PATH)? else {
+        return Ok(None);
+    };
+    let index = json_edit
+        ::elements(contents, array)?
+        .iter()
+        .position(|element| {
+            serde_json
+                ::from_str::<Package>(&contents[element.start..element.end])
+                .map(|package| package.library.eq_ignore_ascii_case(library))
+                .unwrap_or(false)
+        });
+    Ok(index)
+}
@@ -0,0 +1,367 @@
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+
+// Text level edits of JSON files that keep the formatting (indentation, key order, ...) of the
+// untouched parts, unlike a serde_json round trip. Values are located by their byte span:
+//     { "config": { "windows": { "packages": [ ... ] } } }
+//     find(contents, &["config", "windows", "packages"]) -> span of '[ ... ]'
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    // Exclusive
+    pub end: usize,
+}
+
+// Span of the value at the key path, starting at the root object.
+pub fn find(contents: &str, path: &[&str]) -> Result<Option<Span>, String> {
+    let bytes = contents.as_bytes();
+    let start = skip_whitespace(bytes, 0);
+    let mut span = Span { start, end: skip_value(bytes, start)? };
+
+    for key in path {
+        match member(contents, span, key)? {
+            Some(value) => {
+                span = value;
+            }
+            None => {
+                return Ok(None);
+            }
+        }
+    }
+    Ok(Some(span))
+}
+
+// Spans of the elements of the array at the span.
+pub fn elements(contents: &str, array: Span) -> Result<Vec<Span>, String> {
+    let bytes = contents.as_bytes();
+    if bytes.get(array.start) != Some(&b'[') {
+        return Err(format!("expected an array at offset {}", array.start));
+    }
+
+    let mut elements: Vec<Span> = Vec::new();
+    let mut i = skip_whitespace(bytes, array.start + 1);
+    while i < array.end - 1 {
+        let end = skip_value(bytes, i)?;
+        elements.push(Span { start: i, end });
+        i = skip_whitespace(bytes, end);
+        if bytes.get(i) == Some(&b',') {
+            i = skip_whitespace(bytes, i + 1);
+        }
+    }
+    Ok(elements)
+}
+
+// Appends the value (already serialized, may span multiple lines) to the array at the span.
+// The element is indented like the existing elements, or one level deeper than the array.
+pub fn append_element(contents: &str, array: Span, value: &str) -> Result<String, String> {
+    let existing = elements(contents, array)?;
+    let indent_unit = indent_unit(contents);
+    let newline = newline(contents);
+
+    let mut edited = String::new();
+    match existing.last() {
+        Some(last) => {
+            let indent = line_indent(contents, last.start);
+            edited.push_str(&contents[..last.end]);
+            edited.push(',');
+            edited.push_str(newline);
+            edited.push_str(&indent);
+            edited.push_str(&reindent(value, &indent, newline));
+            edited.push_str(&contents[last.end..]);
+        }
+        None => {
+            let closing_indent = line_indent(contents, array.start);
+            let indent = format!("{}{}", closing_indent, indent_unit);
+            edited.push_str(&contents[..array.start + 1]);
+            edited.push_str(newline);
+            edited.push_str(&indent);
+            edited.push_str(&reindent(value, &indent, newline));
+            edited.push_str(newline);
+            edited.push_str(&closing_indent);
+            edited.push_str(&contents[array.end - 1..]);
+        }
+    }
+    Ok(edited)
+}
+
+// Removes the element at the index from the array at the span, with its separator and line.
+pub fn remove_element(contents: &str, array: Span, index: usize) -> Result<String, String> {
+    let existing = elements(contents, array)?;
+    let Some(element) = existing.get(index) else {
+        return Err(format!("no element {} in the array", index));
+    };
+
+    let (start, end) = if existing.len() == 1 {
+        // Leaves an empty '[]'
+        (array.start + 1, array.end - 1)
+    } else if index > 0 {
+        (existing[index - 1].end, element.end)
+    } else {
+        (element.start, existing[1].start)
+    };
+
+    Ok(format!("{}{}", &contents[..start], &contents[end..]))
+}
+
+// Indentation used by the file, the leading whitespace of the first indented line.
+pub fn indent_unit(contents: &str) -> String {
+    contents
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("    ")
+        .to_string()
+}
+
+fn member(contents: &str, object: Span, key: &str) -> Result<Option<Span>, String> {
+    let bytes = contents.as_bytes();
+    if bytes.get(object.start) != Some(&b'{') {
+        return Err(format!("expected an object for '{}'", key));
+    }
+
+    let mut i = skip_whitespace(bytes, object.start + 1);
+    while i < object.end - 1 {
+        let key_end = skip_string(bytes, i)?;
+        let name = &contents[i + 1..key_end - 1];
+        i = skip_whitespace(bytes, key_end);
+        if bytes.get(i) != Some(&b':') {
+            return Err(format!("expected ':' after '{}'", name));
+        }
+        let value_start = skip_whitespace(bytes, i + 1);
+        let value_end = skip_value(bytes, value_start)?;
+        if name == key {
+            return Ok(Some(Span { start: value_start, end: value_end }));
+        }
+        i = skip_whitespace(bytes, value_end);
+        if bytes.get(i) == Some(&b',') {
+            i = skip_whitespace(bytes, i + 1);
+        }
+    }
+    Ok(None)
+}
+
+// Returns the offset right after the value starting at i.
+fn skip_value(bytes: &[u8], i: usize) -> Result<usize, String> {
+    match bytes.get(i) {
+        Some(b'"') => skip_string(bytes, i),
+        Some(b'{') | Some(b'[') => {
+            let mut nesting = 0;
+            let mut j = i;
+            while j < bytes.len() {
+                match bytes[j] {
+                    b'"' => {
+                        j = skip_string(bytes, j)?;
+                        continue;
+                    }
+                    b'{' | b'[' => {
+                        nesting += 1;
+                    }
+                    b'}' | b']' => {
+                        nesting -= 1;
+                        if nesting == 0 {
+                            return Ok(j + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                j += 1;
+            }
+            Err(format!("unclosed '{}' at offset {}", bytes[i] as char, i))
+        }
+        Some(_) => {
+            // Numbers, true, false and null
+            let mut j = i;
+            let is_end = |c: u8| matches!(c, b',' | b'}' | b']') || c.is_ascii_whitespace();
+            while j < bytes.len() && !is_end(bytes[j]) {
+                j += 1;
+            }
+            if j == i {
+                return Err(format!("expected a value at offset {}", i));
+            }
+            Ok(j)
+        }
+        None => Err("unexpected end of the file".to_string()),
+    }
+}
+
+fn skip_string(bytes: &[u8], i: usize) -> Result<usize, String> {
+    if bytes.get(i) != Some(&b'"') {
+        return Err(format!("expected a string at offset {}", i));
+    }
+    let mut j = i + 1;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => {
+                j += 2;
+            }
+            b'"' => {
+                return Ok(j + 1);
+            }
+            _ => {
+                j += 1;
+            }
+        }
+    }
+    Err(format!("unclosed string at offset {}", i))
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+// Leading whitespace of the line the offset is on.
+fn line_indent(contents: &str, offset: usize) -> String {
+    let line_start = contents[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = contents[line_start..].lines().next().unwrap_or("");
+    line[..line.len() - line.trim_start().len()].to_string()
+}
+
+// Serializes the value to replace the contents, with their indentation and line endings.
+pub fn to_string_like(value: &Value, contents: &str) -> Result<String, String> {
+    let indent_unit = indent_unit(contents);
+    let mut serialized = Vec::new();
+    let formatter = PrettyFormatter::with_indent(indent_unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut serialized, formatter);
+    value.serialize(&mut serializer).map_err(|e| e.to_string())?;
+    let serialized = String::from_utf8(serialized).map_err(|e| e.to_string())?;
+    Ok(format!("{}\n", serialized).replace('\n', newline(contents)))
+}
+
+// Line ending used by the file, CRLF if any line ends with it.
+fn newline(contents: &str) -> &'static str {
+    if contents.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+// Indents every line but the first (it is placed after the indentation already).
+fn reindent(value: &str, indent: &str, newline: &str) -> String {
+    value.replace("\r\n", "\n").replace('\n', &format!("{}{}", newline, indent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+  "name": "demo",
+  "config": {
+    "note": "a \"quoted\" [value] {with} brackets",
+    "windows": {
+      "packages": [
+        { "library": "fmt" },
+        { "library": "zlib" }
+      ]
+    }
+  }
+}
+"#;
+
+    fn packages(contents: &str) -> Span {
+        find(contents, &["config", "windows", "packages"]).unwrap().unwrap()
+    }
+
+    #[test]
+    fn finds_nested_values() {
+        let span = find(CONFIG, &["name"]).unwrap().unwrap();
+        assert_eq!(&CONFIG[span.start..span.end], "\"demo\"");
+        let span = find(CONFIG, &["config", "note"]).unwrap().unwrap();
+        assert_eq!(&CONFIG[span.start..span.end], r#""a \"quoted\" [value] {with} brackets""#);
+        assert_eq!(find(CONFIG, &["config", "linux"]).unwrap(), None);
+        assert!(find(CONFIG, &["name", "first"]).is_err());
+    }
+
+    #[test]
+    fn lists_elements() {
+        let array = packages(CONFIG);
+        let elements = elements(CONFIG, array).unwrap();
+        let texts: Vec<&str> = elements
+            .iter()
+            .map(|span| &CONFIG[span.start..span.end])
+            .collect();
+        assert_eq!(texts, vec![r#"{ "library": "fmt" }"#, r#"{ "library": "zlib" }"#]);
+    }
+
+    #[test]
+    fn appends_and_removes_without_touching_the_rest() {
+        let value = "{\n  \"library\": \"curl\"\n}";
+        let added = append_element(CONFIG, packages(CONFIG), value).unwrap();
+        let expected =
+            "{ \"library\": \"zlib\" },\n        {\n          \"library\": \"curl\"\n        }\n      ]";
+        assert!(added.contains(expected));
+
+        let removed = remove_element(&added, packages(&added), 2).unwrap();
+        assert_eq!(removed, CONFIG);
+    }
+
+    #[test]
+    fn removes_the_first_and_the_only_element() {
+        let removed = remove_element(CONFIG, packages(CONFIG), 0).unwrap();
+        assert!(removed.contains("[\n        { \"library\": \"zlib\" }\n      ]"));
+
+        let removed = remove_element(&removed, packages(&removed), 0).unwrap();
+        assert!(removed.contains("\"packages\": []"));
+        assert!(remove_element(&removed, packages(&removed), 0).is_err());
+    }
+
+    #[test]
+    fn appends_to_an_empty_array() {
+        let contents = "{\n\t\"list\": []\n}";
+        let array = find(contents, &["list"]).unwrap().unwrap();
+        let added = append_element(contents, array, "1").unwrap();
+        assert_eq!(added, "{\n\t\"list\": [\n\t\t1\n\t]\n}");
+    }
+
+    #[test]
+    fn handles_trailing_commas() {
+        let contents = "{ \"list\": [1, 2,], \"other\": true, }";
+        let array = find(contents, &["list"]).unwrap().unwrap();
+        assert_eq!(elements(contents, array).unwrap().len(), 2);
+        let other = find(contents, &["other"]).unwrap().unwrap();
+        assert_eq!(&contents[other.start..other.end], "true");
+
+        let removed = remove_element(contents, array, 1).unwrap();
+        assert_eq!(removed, "{ \"list\": [1,], \"other\": true, }");
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let contents = CONFIG.replace('\n', "\r\n");
+        let added = append_element(&contents, packages(&contents), "{\n  \"a\": 1\n}").unwrap();
+        assert_eq!(added.matches('\n').count(), added.matches("\r\n").count());
+        assert!(added.contains("},\r\n        {\r\n          \"a\": 1\r\n        }\r\n      ]"));
+
+        let removed = remove_element(&added, packages(&added), 2).unwrap();
+        assert_eq!(removed, contents);
+    }
+
+    #[test]
+    fn serializes_like_the_replaced_contents() {
+        let value = serde_json::json!({ "a": [1] });
+        assert_eq!(
+            to_string_like(&value, "").unwrap(),
+            "{\n    \"a\": [\n        1\n    ]\n}\n"
+        );
+        assert_eq!(
+            to_string_like(&value, "{\r\n\t\"old\": 1\r\n}\r\n").unwrap(),
+            "{\r\n\t\"a\": [\r\n\t\t1\r\n\t]\r\n}\r\n"
+        );
+    }
+
+    #[test]
+    fn detects_the_indentation() {
+        assert_eq!(indent_unit(CONFIG), "  ");
+        assert_eq!(indent_unit("{\n\t\"a\": 1\n}"), "\t");
+        assert_eq!(indent_unit("{}"), "    ");
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(find("{ \"a\": [1, 2 }", &["a"]).is_err());
+        assert!(find("{ \"a\" 1 }", &["a"]).is_err());
+        assert!(find("{ \"a\": \"open }", &["a"]).is_err());
+        assert!(find("", &[]).is_err());
+    }
+}
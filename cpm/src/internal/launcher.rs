@@ -0,0 +1,86 @@
+use crate::internal::cmd;
+use crate::internal::configure_command::{ ConfigureCommand, Definition };
+
+// Compiler launchers (compiler caches) CMake can put in front of every compile:
+//     -DCMAKE_C_COMPILER_LAUNCHER=<path> -DCMAKE_CXX_COMPILER_LAUNCHER=<path>
+// Only honored by the Ninja and Makefile generators, Visual Studio generators ignore them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Launcher {
+    Ccache,
+    Sccache,
+}
+
+pub const LANGUAGES: [&str; 2] = ["C", "CXX"];
+
+impl Launcher {
+    pub fn parse(launcher: &str) -> Option<Self> {
+        match launcher.to_lowercase().as_str() {
+            "ccache" => Some(Launcher::Ccache),
+            "sccache" => Some(Launcher::Sccache),
+            _ => None,
+        }
+    }
+
+    // Guesses the launcher from the program path, i.e. '/usr/bin/ccache'
+    pub fn from_path(path: &str) -> Option<Self> {
+        let name = std::path::Path::new(path).file_stem()?.to_string_lossy().to_lowercase();
+        Self::parse(&name)
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            Launcher::Ccache => "ccache",
+            Launcher::Sccache => "sccache",
+        }
+    }
+
+    // Returns the path of the launcher if it is on PATH.
+    pub fn find(&self) -> Option<String> {
+        cmd::find_program(self.program())
+    }
+}
+
+// First launcher found on PATH, ccache is preferred.
+pub fn detect() -> Option<(Launcher, String)> {
+    [Launcher::Ccache, Launcher::Sccache]
+        .into_iter()
+        .find_map(|launcher| launcher.find().map(|path| (launcher, path)))
+}
+
+// The launcher a configure command was generated with, if any.
+pub fn configured(configure_command: &[String]) -> Option<(Launcher, String)> {
+    let command = ConfigureCommand::parse(configure_command);
+    LANGUAGES.iter().find_map(|language| {
+        let key = format!("CMAKE_{}_COMPILER_LAUNCHER", language);
+        let definition = command.definitions.iter().find(|d| d.key == key)?;
+        Launcher::from_path(&definition.value).map(|launcher| (launcher, definition.value.clone()))
+    })
+}
+
+// Both ccache and sccache understand the same flags for their statistics.
+pub fn stats_command(program_path: &str) -> Vec<String> {
+    vec![program_path.to_string(), "--show-stats".to_string()]
+}
+
+pub fn zero_stats_command(program_path: &str) -> Vec<String> {
+    vec![program_path.to_string(), "--zero-stats".to_string()]
+}
+
+// Sets the launcher for every language, or removes it (also from the CMake cache) if 'launcher_path' is None.
+pub fn apply(configure_command: Vec<String>, launcher_path: Option<&str>) -> Vec<String> {
+    let mut command = ConfigureCommand::parse(&configure_command);
+    for language in LANGUAGES {
+        let key = format!("CMAKE_{}_COMPILER_LAUNCHER", language);
+        match launcher_path {
+            Some(path) => {
+                command.set_definition(Definition {
+                    key,
+                    value_type: Some("FILEPATH".to_string()),
+                    value: path.to_string(),
+                });
+            }
+            None => command.unset_definition(&key),
+        }
+    }
+    command.to_argv()
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{ self, Read, Write };
+use std::path::Path;
+
+// Reader/writer for lcov tracefiles (as written by 'lcov --capture' and 'llvm-cov export -format=lcov').
+// Every source file is one record:
+//     SF:<path>
+//     DA:<line>,<hits>
+//     LF:<lines found>
+//     LH:<lines hit>
+//     end_of_record
+// Records are kept as raw text so filtered tracefiles keep everything the tools wrote. A file can
+// appear in more than one record (i.e. a header used by several test executables), its records are
+// merged so the file is counted once.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub source_file: String,
+    pub lines_found: usize,
+    pub lines_hit: usize,
+    lines: Vec<String>,
+    // Hits per line of the DA lines
+    hits: BTreeMap<u64, u64>,
+}
+
+impl Record {
+    pub fn percent(&self) -> f64 {
+        percent(self.lines_hit, self.lines_found)
+    }
+
+    // A line is hit if any of the records hit it. Without DA lines only the totals are known.
+    fn merge(&mut self, other: Record) {
+        self.lines.extend(other.lines);
+        if self.hits.is_empty() || other.hits.is_empty() {
+            self.lines_found = self.lines_found.max(other.lines_found);
+            self.lines_hit = self.lines_hit.max(other.lines_hit);
+            self.hits.extend(other.hits);
+            return;
+        }
+        for (line_number, hits) in other.hits {
+            *self.hits.entry(line_number).or_default() += hits;
+        }
+        self.lines_found = self.hits.len();
+        self.lines_hit = self.hits
+            .values()
+            .filter(|hits| **hits > 0)
+            .count();
+    }
+}
+
+pub fn percent(hit: usize, found: usize) -> f64 {
+    if found == 0 {
+        100.0
+    } else {
+        ((hit as f64) * 100.0) / (found as f64)
+    }
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    let mut file = File::open(path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    Ok(parse(&contents))
+}
+
+pub fn parse(contents: &str) -> Vec<Record> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut current: Option<Record> = None;
+    // LF/LH are optional, fall back to counting DA lines
+    let mut counted_found = 0;
+    let mut counted_hit = 0;
+    let mut has_totals = false;
+
+    for line in contents.lines() {
+        let line = line.trim_end_matches('\r');
+
+        if let Some(source_file) = line.strip_prefix("SF:") {
+            current = Some(Record {
+                source_file: source_file.to_string(),
+                lines_found: 0,
+                lines_hit: 0,
+                lines: Vec::new(),
+                hits: BTreeMap::new(),
+            });
+            counted_found = 0;
+            counted_hit = 0;
+            has_totals = false;
+        }
+
+        let Some(record) = current.as_mut() else {
+            continue;
+        };
+        record.lines.push(line.to_string());
+
+        if let Some(data) = line.strip_prefix("DA:") {
+            let mut fields = data.split(',').map(|field| field.parse::<u64>().ok());
+            let line_number = fields.next().flatten();
+            let hits = fields.next().flatten().unwrap_or(0);
+            counted_found += 1;
+            if hits > 0 {
+                counted_hit += 1;
+            }
+            if let Some(line_number) = line_number {
+                *record.hits.entry(line_number).or_default() += hits;
+            }
+        } else if let Some(found) = line.strip_prefix("LF:") {
+            record.lines_found = found.parse().unwrap_or(0);
+            has_totals = true;
+        } else if let Some(hit) = line.strip_prefix("LH:") {
+            record.lines_hit = hit.parse().unwrap_or(0);
+        } else if line == "end_of_record" {
+            let mut record = current.take().unwrap();
+            if !has_totals {
+                record.lines_found = counted_found;
+                record.lines_hit = counted_hit;
+            }
+            match records.iter_mut().find(|r| r.source_file == record.source_file) {
+                Some(existing) => existing.merge(record),
+                None => records.push(record),
+            }
+        }
+    }
+
+    records
+}
+
+pub fn write(path: &Path, records: &[Record]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    // Test name line, required by genhtml
+    writeln!(file, "TN:")?;
+    for record in records {
+        for line in &record.lines {
+            writeln!(file, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEFILE: &str = "\
+TN:
+SF:C:/project/src/main.cpp
+FN:3,main
+FNDA:1,main
+DA:3,1
+DA:4,1
+DA:6,0
+BRDA:4,0,0,1
+BRDA:4,0,1,0
+BRF:2
+BRH:1
+LF:3
+LH:2
+end_of_record
+SF:C:/project/src/util.cpp
+DA:1,5
+DA:2,0
+end_of_record
+";
+
+    #[test]
+    fn parses_records_with_their_totals() {
+        let records = parse(TRACEFILE);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].source_file, "C:/project/src/main.cpp");
+        assert_eq!((records[0].lines_found, records[0].lines_hit), (3, 2));
+        assert!((records[0].percent() - 66.666).abs() < 0.01);
+
+        // Without LF/LH the DA lines are counted
+        assert_eq!((records[1].lines_found, records[1].lines_hit), (2, 1));
+    }
+
+    #[test]
+    fn keeps_branch_and_function_lines() {
+        let records = parse(TRACEFILE);
+        for line in ["FN:3,main", "BRDA:4,0,1,0", "BRF:2", "BRH:1", "end_of_record"] {
+            assert!(records[0].lines.iter().any(|l| l == line), "{}", line);
+        }
+        // Lines before the first record (the test name) are not part of any record
+        assert_eq!(records[0].lines[0], "SF:C:/project/src/main.cpp");
+    }
+
+    #[test]
+    fn merges_records_of_the_same_file() {
+        let contents = "\
+SF:include/util.hpp
+DA:1,1
+DA:2,0
+LF:2
+LH:1
+end_of_record
+SF:src/main.cpp
+DA:1,1
+end_of_record
+SF:include/util.hpp
+DA:2,3
+DA:3,0
+LF:2
+LH:1
+end_of_record
+";
+        let records = parse(contents);
+        assert_eq!(records.len(), 2);
+        let header = &records[0];
+        assert_eq!(header.source_file, "include/util.hpp");
+        assert_eq!((header.lines_found, header.lines_hit), (3, 2));
+        assert_eq!(header.lines.iter().filter(|l| *l == "end_of_record").count(), 2);
+    }
+
+    #[test]
+    fn merges_records_without_line_data() {
+        let contents = "SF:a.cpp\nLF:4\nLH:1\nend_of_record\nSF:a.cpp\nLF:4\nLH:3\nend_of_record\n";
+        let records = parse(contents);
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].lines_found, records[0].lines_hit), (4, 3));
+    }
+
+    #[test]
+    fn ignores_unterminated_records_and_parses_crlf() {
+        let records = parse(&TRACEFILE.replace('\n', "\r\n"));
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].lines_found, records[0].lines_hit), (3, 2));
+        assert!(records[0].lines.iter().all(|line| !line.ends_with('\r')));
+
+        assert!(parse("SF:a.cpp\nDA:1,1\n").is_empty());
+        assert_eq!(percent(0, 0), 100.0);
+    }
+}
@@ -2,3 +2,28 @@ pub mod settings;
 pub mod logger;
 pub mod install;
 pub mod cmd;
+pub mod file_api;
+pub mod compile_commands;
+pub mod stage;
+pub mod fingerprint;
+pub mod configure_command;
+pub mod cmake_cache;
+pub mod diagnostics;
+pub mod build_report;
+pub mod ninja_log;
+pub mod launcher;
+pub mod variant;
+pub mod lcov;
+pub mod templates;
+pub mod template_engine;
+pub mod cmake_lists;
+pub mod json_edit;
+pub mod package_usage;
+pub mod entrypoint;
+pub mod project_root;
+pub mod gitignore;
+pub mod editor_config;
+pub mod vscode;
+pub mod ide_profile;
+pub mod visual_studio;
+pub mod clion;
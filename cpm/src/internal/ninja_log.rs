@@ -0,0 +1,206 @@
+use serde::{ Serialize, Deserialize };
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{ self, Read };
+use std::path::{ Path, PathBuf };
+
+// Parser for the Ninja build log '<build_dir>/.ninja_log'. After the '# ninja log vN' header every
+// line is one finished edge:
+//     <start ms>\t<end ms>\t<mtime>\t<output>\t<command hash>
+// Start and end are relative to the start of the ninja invocation and entries of every run are
+// appended, so a new run begins where the end time goes backwards.
+pub const FILE_NAME: &str = ".ninja_log";
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub start: u64,
+    pub end: u64,
+    pub output: String,
+}
+
+impl LogEntry {
+    pub fn duration(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    // Object files are written to 'CMakeFiles/<target>.dir/...', everything else (links, custom
+    // commands) is attributed to its own output.
+    pub fn target(&self) -> String {
+        let output = self.output.replace('\\', "/");
+        match output.split_once("CMakeFiles/").and_then(|(_, rest)| rest.split_once(".dir/")) {
+            Some((target, _)) => target.to_string(),
+            None => output,
+        }
+    }
+}
+
+// Summary of a single ninja run, also cached in the settings to compare against the next run.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BuildProfile {
+    // Size of the log when the run was recorded, identifies the run
+    pub log_size: u64,
+    pub edges: usize,
+    pub wall_ms: u64,
+    // Sum of all edge durations, the time a serial build would take
+    pub cpu_ms: u64,
+    // Summed durations per target
+    pub targets: BTreeMap<String, u64>,
+    // Durations of the slowest outputs
+    pub slowest: BTreeMap<String, u64>,
+}
+
+pub fn log_path(build_dir: &Path) -> PathBuf {
+    build_dir.join(FILE_NAME)
+}
+
+pub fn load(build_dir: &Path) -> io::Result<(Vec<LogEntry>, u64)> {
+    let mut file = File::open(log_path(build_dir))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    Ok((parse(&contents), contents.len() as u64))
+}
+
+pub fn parse(contents: &str) -> Vec<LogEntry> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut columns = line.split('\t');
+            let start = columns.next()?.parse().ok()?;
+            let end = columns.next()?.parse().ok()?;
+            let _mtime = columns.next()?;
+            let output = columns.next()?.to_string();
+            Some(LogEntry { start, end, output })
+        })
+        .collect()
+}
+
+// Entries of the most recent ninja run. An output built more than once keeps its last entry.
+pub fn last_run(entries: &[LogEntry]) -> Vec<LogEntry> {
+    let mut run_start = 0;
+    let mut last_end = 0;
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.end < last_end {
+            run_start = index;
+        }
+        last_end = entry.end;
+    }
+
+    let mut run: Vec<LogEntry> = Vec::new();
+    for entry in &entries[run_start..] {
+        run.retain(|e| e.output != entry.output);
+        run.push(entry.clone());
+    }
+    run
+}
+
+pub fn profile(run: &[LogEntry], log_size: u64, top: usize) -> BuildProfile {
+    let mut profile = BuildProfile {
+        log_size,
+        edges: run.len(),
+        ..Default::default()
+    };
+
+    let first_start = run.iter().map(|e| e.start).min().unwrap_or(0);
+    let last_end = run.iter().map(|e| e.end).max().unwrap_or(0);
+    profile.wall_ms = last_end.saturating_sub(first_start);
+
+    for entry in run {
+        profile.cpu_ms += entry.duration();
+        *profile.targets.entry(entry.target()).or_default() += entry.duration();
+    }
+
+    let mut slowest: Vec<&LogEntry> = run.iter().collect();
+    slowest.sort_by_key(|e| std::cmp::Reverse(e.duration()));
+    for entry in slowest.into_iter().take(top) {
+        profile.slowest.insert(entry.output.clone(), entry.duration());
+    }
+
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outputs(entries: &[LogEntry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|entry| entry.output.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parses_entries_and_skips_invalid_lines() {
+        let contents = "\
+# ninja log v5
+0\t120\t0\tCMakeFiles/app.dir/src/main.cpp.obj\t1a2b
+not a log line
+10\tx\t0\tbroken\t1a2b
+120\t150\t0\tapp.exe\t3c4d
+";
+        let entries = parse(contents);
+        assert_eq!(outputs(&entries), vec!["CMakeFiles/app.dir/src/main.cpp.obj", "app.exe"]);
+        assert_eq!(entries[0].duration(), 120);
+        assert_eq!(entries[1].start, 120);
+    }
+
+    #[test]
+    fn parses_crlf_logs() {
+        let entries = parse("# ninja log v5\r\n0\t5\t0\ta.obj\t1\r\n");
+        assert_eq!(outputs(&entries), vec!["a.obj"]);
+    }
+
+    #[test]
+    fn keeps_the_last_run_after_restarts() {
+        let contents = "\
+# ninja log v5
+0\t100\t0\ta.obj\t1
+0\t200\t0\tb.obj\t2
+200\t300\t0\tapp.exe\t3
+0\t50\t0\ta.obj\t1
+50\t80\t0\tapp.exe\t3
+0\t20\t0\tb.obj\t2
+20\t40\t0\tapp.exe\t3
+";
+        let run = last_run(&parse(contents));
+        assert_eq!(outputs(&run), vec!["b.obj", "app.exe"]);
+        assert_eq!(run[1].duration(), 20);
+    }
+
+    #[test]
+    fn keeps_the_last_entry_of_an_output_built_twice() {
+        let contents = "0\t10\t0\tgen.h\t1\n10\t30\t0\tgen.h\t2\n30\t40\t0\tapp.exe\t3\n";
+        let run = last_run(&parse(contents));
+        assert_eq!(outputs(&run), vec!["gen.h", "app.exe"]);
+        assert_eq!(run[0].duration(), 20);
+        assert!(last_run(&[]).is_empty());
+    }
+
+    #[test]
+    fn attributes_outputs_to_targets() {
+        let entry = |output: &str| LogEntry { start: 0, end: 0, output: output.to_string() };
+        assert_eq!(entry("CMakeFiles/app.dir/src/main.cpp.obj").target(), "app");
+        assert_eq!(entry("lib\\CMakeFiles\\core.dir\\src\\a.cpp.obj").target(), "core");
+        assert_eq!(entry("bin/app.exe").target(), "bin/app.exe");
+    }
+
+    #[test]
+    fn profiles_a_run() {
+        let contents = "\
+10\t110\t0\tCMakeFiles/app.dir/a.obj\t1
+10\t60\t0\tCMakeFiles/app.dir/b.obj\t2
+110\t130\t0\tapp.exe\t3
+";
+        let profile = profile(&parse(contents), 42, 2);
+        assert_eq!(profile.log_size, 42);
+        assert_eq!(profile.edges, 3);
+        assert_eq!(profile.wall_ms, 120);
+        assert_eq!(profile.cpu_ms, 170);
+        assert_eq!(profile.targets["app"], 150);
+        assert_eq!(profile.targets["app.exe"], 20);
+        assert_eq!(profile.slowest.len(), 2);
+        assert_eq!(profile.slowest["CMakeFiles/app.dir/a.obj"], 100);
+        assert_eq!(profile.slowest["CMakeFiles/app.dir/b.obj"], 50);
+    }
+}
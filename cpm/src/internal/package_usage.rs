@@ -0,0 +1,164 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+// How to consume an installed vcpkg port from CMake. Read from (first found):
+//     <vcpkg>/installed/<triplet>/share/<port>/usage   ---> written by the port, i.e.
+//         find_package(fmt CONFIG REQUIRED)
+//         target_link_libraries(main PRIVATE fmt::fmt)
+//     <vcpkg>/ports/<port>/usage
+//     <vcpkg>/installed/<triplet>/share/<port>/*Config.cmake and *Targets.cmake ---> package and IMPORTED targets
+#[derive(Debug, Clone, Default)]
+pub struct Usage {
+    // The find_package call, i.e. 'find_package(fmt CONFIG REQUIRED)'
+    pub find_package: Option<String>,
+    pub link_targets: Vec<String>,
+    // Raw usage text, shown as it is when the calls could not be extracted
+    pub text: String,
+}
+
+impl Usage {
+    // Name passed to find_package
+    pub fn package(&self) -> Option<String> {
+        self.find_package
+            .as_ref()
+            .and_then(|call| arguments(call).into_iter().next())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.find_package.is_some() && !self.link_targets.is_empty()
+    }
+
+    pub fn link_call(&self, target: &str) -> String {
+        format!("target_link_libraries({} PRIVATE {})", target, self.link_targets.join(" "))
+    }
+}
+
+pub fn share_dir(toolchain_path: &str, triplet: &str, port: &str) -> PathBuf {
+    Path::new(toolchain_path).join("installed").join(triplet).join("share").join(port)
+}
+
+pub fn load(toolchain_path: &str, triplet: &str, port: &str) -> Option<Usage> {
+    let share_dir = share_dir(toolchain_path, triplet, port);
+    let usage_files = [
+        share_dir.join("usage"),
+        Path::new(toolchain_path).join("ports").join(port).join("usage"),
+    ];
+
+    for usage_file in usage_files {
+        if let Ok(contents) = fs::read_to_string(&usage_file) {
+            return Some(parse(&contents));
+        }
+    }
+    from_config_files(&share_dir)
+}
+
+pub fn parse(contents: &str) -> Usage {
+    let calls = calls(contents);
+    let find_package = calls
+        .iter()
+        .find(|call| call.starts_with("find_package("))
+        .cloned();
+    let link_targets = calls
+        .iter()
+        .find(|call| call.starts_with("target_link_libraries("))
+        .map(|call| {
+            arguments(call)
+                .into_iter()
+                .skip(1)
+                .filter(|argument| !matches!(argument.as_str(), "PRIVATE" | "PUBLIC" | "INTERFACE"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Usage { find_package, link_targets, text: contents.trim_end().to_string() }
+}
+
+// find_package()/target_link_libraries() calls of the usage text, joined on one line.
+fn calls(contents: &str) -> Vec<String> {
+    let mut calls: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        let is_call =
+            line.starts_with("find_package(") || line.starts_with("target_link_libraries(");
+        let call = match current.take() {
+            Some(call) => format!("{} {}", call, line),
+            None if is_call => line.to_string(),
+            None => {
+                continue;
+            }
+        };
+
+        if call.matches('(').count() > call.matches(')').count() {
+            current = Some(call);
+        } else {
+            calls.push(call.split_whitespace().collect::<Vec<&str>>().join(" "));
+        }
+    }
+    calls
+}
+
+// Arguments of a single line call, i.e. 'find_package(fmt CONFIG REQUIRED)' -> [fmt, CONFIG, REQUIRED]
+fn arguments(call: &str) -> Vec<String> {
+    let Some((_, rest)) = call.split_once('(') else {
+        return Vec::new();
+    };
+    rest.strip_suffix(')')
+        .unwrap_or(rest)
+        .split_whitespace()
+        .map(|argument| argument.trim_matches('"').to_string())
+        .collect()
+}
+
+// Ports without a usage file: the package name comes from '<Package>Config.cmake' or
+// '<package>-config.cmake' and the targets from the IMPORTED targets of the '*Targets.cmake' files.
+fn from_config_files(share_dir: &Path) -> Option<Usage> {
+    let mut files: Vec<PathBuf> = fs
+        ::read_dir(share_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    files.sort();
+
+    let file_name = |path: &PathBuf| {
+        path.file_name().unwrap_or_default().to_string_lossy().to_string()
+    };
+
+    let package = files.iter().find_map(|path| {
+        let name = file_name(path);
+        name.strip_suffix("Config.cmake")
+            .or(name.strip_suffix("-config.cmake"))
+            .map(|package| package.to_string())
+    })?;
+
+    let mut link_targets: Vec<String> = Vec::new();
+    for path in &files {
+        let name = file_name(path);
+        if !(name.ends_with("Targets.cmake") || name.ends_with("-targets.cmake")) {
+            continue;
+        }
+        let Ok(contents) = fs::read_to_string(path) else {
+            continue;
+        };
+        for line in contents.lines() {
+            let line = line.trim();
+            if !(line.starts_with("add_library(") && line.contains("IMPORTED")) {
+                continue;
+            }
+            if let Some(target) = arguments(line).into_iter().next() {
+                if !link_targets.contains(&target) {
+                    link_targets.push(target);
+                }
+            }
+        }
+    }
+
+    let find_package = format!("find_package({} CONFIG REQUIRED)", package);
+    let mut text = find_package.clone();
+    if !link_targets.is_empty() {
+        text.push_str(&format!("\ntarget_link_libraries(main PRIVATE {})", link_targets.join(" ")));
+    }
+    Some(Usage { find_package: Some(find_package), link_targets, text })
+}
@@ -0,0 +1,44 @@
+use std::path::{ Path, PathBuf };
+
+use crate::internal::cmake_lists;
+
+// The project root is found by searching upward from the current directory, so CPM can be run from
+// any subdirectory of the project. Markers, in priority order:
+//     cpm_install.json or .cpm/            ---> the nearest directory that has one
+//     CMakeLists.txt with a project() call ---> the nearest one
+pub const CONFIG_FILE: &str = "cpm_install.json";
+pub const CPM_DIR: &str = ".cpm";
+
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    let has_cpm_marker = |dir: &Path| dir.join(CONFIG_FILE).is_file() || dir.join(CPM_DIR).is_dir();
+    if let Some(root) = start.ancestors().find(|dir| has_cpm_marker(dir)) {
+        return Some(root.to_path_buf());
+    }
+
+    start
+        .ancestors()
+        .find(|dir| calls_project(dir))
+        .map(|root| root.to_path_buf())
+}
+
+// Project root of the current directory, or the current directory itself when there is none.
+pub fn current() -> std::io::Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    Ok(discover(&current_dir).unwrap_or(current_dir))
+}
+
+// Whether the path is the root or inside of it. Both are resolved first, so differences in case
+// (Windows) or symbolic links do not matter.
+pub fn is_within(path: &Path, root: &Path) -> bool {
+    let resolve = |path: &Path| path.canonicalize().unwrap_or(path.to_path_buf());
+    resolve(path).starts_with(resolve(root))
+}
+
+fn calls_project(dir: &Path) -> bool {
+    match cmake_lists::load(&cmake_lists::file_path(dir)) {
+        Ok((_, commands)) => {
+            commands.iter().any(|command| command.depth == 0 && command.name == "project")
+        }
+        Err(_) => false,
+    }
+}
@@ -1,9 +1,12 @@
 use serde::{ Serialize, Deserialize };
+use std::collections::BTreeMap;
 use std::fs::{ self, File };
 use std::io::{ self, Read, Write };
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::internal::ninja_log::BuildProfile;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Settings {
     // Basic information
@@ -18,6 +21,9 @@ pub struct Settings {
     // Required for building project
     pub build_dir: String,
     pub install_dir: String,
+    // Added after 1.0.0, default keeps older settings files loadable
+    #[serde(default)]
+    pub dist_dir: String,
     pub using_toolchain: bool,
     pub toolchain_path: String,
     // WINDOWS ONLY - VCPKG CMAKE TOOLCHAIN
@@ -26,9 +32,25 @@ pub struct Settings {
     // WINDOWS ONLY - VCPKG CMAKE TOOLCHAIN
     pub cmake_system_type: String,
     pub cmake_build_type: String,
+    // Sanitizer/coverage variant of the active build tree, empty for the plain build
+    #[serde(default)]
+    pub build_variant: String,
+    // Compiler launcher used when generating: "", "auto", "ccache", "sccache" or "none"
+    #[serde(default)]
+    pub compiler_launcher: String,
+    // User project templates, empty for '<exe_dir>/templates'
+    #[serde(default)]
+    pub templates_dir: String,
     // Cached commands
     pub last_cmake_configuration_command: Vec<String>,
     pub last_command: Vec<String>,
+    #[serde(default)]
+    pub last_configure_fingerprint: BTreeMap<String, String>,
+    // Ninja build profiles of the last two builds, see 'stats build'
+    #[serde(default)]
+    pub last_build_profile: Option<BuildProfile>,
+    #[serde(default)]
+    pub previous_build_profile: Option<BuildProfile>,
 }
 
 impl Settings {
@@ -55,14 +77,15 @@ impl Settings {
             os: std::env::consts::OS.to_string(),
             os_release: sys_info::os_type().unwrap_or_default(),
             os_version: sys_info::os_release().unwrap_or_default(),
-            exe_path: exe_path,
-            exe_dir: exe_dir,
+            exe_path,
+            exe_dir,
             working_dir: "".to_string(),
             initialized: false,
             install_json_path: "".to_string(),
             // Required for building project
             build_dir: "".to_string(),
             install_dir: "".to_string(),
+            dist_dir: "".to_string(),
             using_toolchain: false,
             toolchain_path: "".to_string(),
             // WINDOWS ONLY - VCPKG CMAKE TOOLCHAIN
@@ -71,9 +94,15 @@ impl Settings {
             // WINDOWS ONLY - VCPKG CMAKE TOOLCHAIN
             cmake_system_type: "".to_string(),
             cmake_build_type: "".to_string(),
+            build_variant: "".to_string(),
+            compiler_launcher: "".to_string(),
+            templates_dir: "".to_string(),
             // Cached commands
             last_cmake_configuration_command: vec![],
             last_command: vec![],
+            last_configure_fingerprint: BTreeMap::new(),
+            last_build_profile: None,
+            previous_build_profile: None,
         })
     }
 
@@ -110,6 +139,25 @@ impl Settings {
         file.write_all(contents.as_bytes())
     }
 
+    // Forgets the initialized project: its directories and the cached commands.
+    // Machine wide settings (toolchain, system type, launcher, ...) are kept.
+    pub fn reset_project(&mut self) {
+        self.initialized = false;
+        self.working_dir = String::new();
+        self.install_json_path = String::new();
+        self.build_dir = String::new();
+        self.install_dir = String::new();
+        self.dist_dir = String::new();
+        self.cmake_system_type = String::new();
+        self.cmake_build_type = String::new();
+        self.build_variant = String::new();
+        self.last_cmake_configuration_command = vec![];
+        self.last_command = vec![];
+        self.last_configure_fingerprint = BTreeMap::new();
+        self.last_build_profile = None;
+        self.previous_build_profile = None;
+    }
+
     // Saves the settings to the default path '<exe_dir>/settings.json'
     // This should be called when we are sure that the settings file exists
     pub fn save_default(&self) -> io::Result<()> {
@@ -136,11 +184,15 @@ impl Settings {
             "install_json_path" => Some(self.install_json_path.clone()),
             "build_dir" => Some(self.build_dir.clone()),
             "install_dir" => Some(self.install_dir.clone()),
+            "dist_dir" => Some(self.dist_dir.clone()),
             "using_toolchain" => Some(self.using_toolchain.to_string()),
             "toolchain_path" => Some(self.toolchain_path.clone()),
             "vcpkg_path" => Some(self.vcpkg_path.clone()),
             "cmake_system_type" => Some(self.cmake_system_type.clone()),
             "cmake_build_type" => Some(self.cmake_build_type.clone()),
+            "build_variant" => Some(self.build_variant.clone()),
+            "compiler_launcher" => Some(self.compiler_launcher.clone()),
+            "templates_dir" => Some(self.templates_dir.clone()),
             // Cached commands are locked
             _ => None,
         }
@@ -175,6 +227,9 @@ impl Settings {
             "install_dir" => {
                 self.install_dir = value;
             }
+            "dist_dir" => {
+                self.dist_dir = value;
+            }
             "using_toolchain" => {
                 self.using_toolchain = value.parse().unwrap_or(false);
             }
@@ -190,6 +245,12 @@ impl Settings {
             "cmake_build_type" => {
                 self.cmake_build_type = value;
             }
+            "compiler_launcher" => {
+                self.compiler_launcher = value;
+            }
+            "templates_dir" => {
+                self.templates_dir = value;
+            }
             // Cached commands are locked
             _ => {
                 return Err("Key not found".to_string());
@@ -201,7 +262,8 @@ impl Settings {
     }
 
     pub fn contains_key(&self, key: &str) -> bool {
-        match key {
+        matches!(
+            key,
             | "os"
             | "os_release"
             | "os_version"
@@ -211,14 +273,17 @@ impl Settings {
             | "install_json_path"
             | "build_dir"
             | "install_dir"
+            | "dist_dir"
             | "using_toolchain"
             | "toolchain_path"
             | "vcpkg_path"
             | "cmake_system_type"
             | "cmake_build_type"
+            | "build_variant"
+            | "compiler_launcher"
+            | "templates_dir"
             | "last_cmake_configuration_command"
-            | "last_command" => true,
-            _ => false,
-        }
+            | "last_command"
+        )
     }
 }
@@ -0,0 +1,228 @@
+use serde::{ Deserialize, Serialize };
+use spdlog::prelude::*;
+use std::collections::BTreeSet;
+use std::fs::{ self, File };
+use std::io::{ self, Read, Write };
+use std::path::{ Path, PathBuf };
+use walkdir::WalkDir;
+
+// Staging mirrors runtime asset directories (shaders, configs, data) next to the executables,
+// so they can be found relative to the executable without an install step.
+// Every staged file is recorded in a manifest in the destination directory so files that
+// disappear from the source can be removed on the next run.
+const MANIFEST_NAME: &str = ".cpm_stage.json";
+
+// Preferred way of mirroring a file. If it fails the next one in the chain is tried:
+// symlink -> hard link -> copy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkMode {
+    Symlink,
+    Hardlink,
+    Copy,
+}
+
+impl LinkMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "symlink" => Some(LinkMode::Symlink),
+            "hardlink" => Some(LinkMode::Hardlink),
+            "copy" => Some(LinkMode::Copy),
+            _ => None,
+        }
+    }
+
+    fn next(&self) -> Option<Self> {
+        match self {
+            LinkMode::Symlink => Some(LinkMode::Hardlink),
+            LinkMode::Hardlink => Some(LinkMode::Copy),
+            LinkMode::Copy => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct StageReport {
+    pub staged: usize,
+    pub up_to_date: usize,
+    pub removed: usize,
+    pub failed: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct StageManifest {
+    // Paths relative to the destination directory
+    files: BTreeSet<String>,
+}
+
+// Mirrors every asset directory into '<destination>/<asset directory name>'.
+pub fn stage_assets(
+    asset_dirs: &[PathBuf],
+    destination: &Path,
+    mode: LinkMode
+) -> io::Result<StageReport> {
+    let mut report = StageReport::default();
+    let manifest_path = destination.join(MANIFEST_NAME);
+    let previous = load_manifest(&manifest_path);
+    let mut current = StageManifest::default();
+
+    for asset_dir in asset_dirs {
+        if !asset_dir.is_dir() {
+            warn!("Asset directory not found, skipping: {:?}", asset_dir);
+            continue;
+        }
+        let asset_name = match asset_dir.file_name() {
+            Some(name) => PathBuf::from(name),
+            None => {
+                warn!("Invalid asset directory, skipping: {:?}", asset_dir);
+                continue;
+            }
+        };
+
+        for entry in WalkDir::new(asset_dir) {
+            let entry = entry?;
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            let relative_path = asset_name.join(
+                path.strip_prefix(asset_dir).map_err(io::Error::other)?
+            );
+            let target_path = destination.join(&relative_path);
+            current.files.insert(relative_path.to_string_lossy().to_string());
+
+            if is_up_to_date(path, &target_path) {
+                report.up_to_date += 1;
+                continue;
+            }
+
+            // Ensure the target directory exists
+            if let Some(parent) = target_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            match link_file(path, &target_path, mode) {
+                Ok(used) => {
+                    trace!("Staged {:?} ({:?})", relative_path, used);
+                    report.staged += 1;
+                }
+                Err(e) => {
+                    error!("Failed to stage {:?}: {}", path, e);
+                    report.failed += 1;
+                }
+            }
+        }
+    }
+
+    // Remove files that were staged before but no longer exist in the source
+    for stale in previous.files.difference(&current.files) {
+        let stale_path = destination.join(stale);
+        match remove_file(&stale_path) {
+            Ok(_) => {
+                trace!("Removed stale asset {:?}", stale_path);
+                report.removed += 1;
+            }
+            Err(e) => warn!("Failed to remove stale asset {:?}: {}", stale_path, e),
+        }
+        remove_empty_parents(&stale_path, destination);
+    }
+
+    save_manifest(&manifest_path, &current)?;
+    Ok(report)
+}
+
+// Creates the link (or copy), replacing an existing file. Falls back down the chain on failure.
+// Returns the mode that succeeded.
+pub fn link_file(source: &Path, destination: &Path, mode: LinkMode) -> io::Result<LinkMode> {
+    remove_file(destination)?;
+
+    let result = match mode {
+        LinkMode::Symlink => symlink_file(source, destination),
+        LinkMode::Hardlink => fs::hard_link(source, destination),
+        LinkMode::Copy => fs::copy(source, destination).map(|_| ()),
+    };
+
+    match result {
+        Ok(_) => Ok(mode),
+        Err(e) =>
+            match mode.next() {
+                Some(next) => {
+                    debug!("{:?} failed for {:?}, trying {:?}: {}", mode, destination, next, e);
+                    link_file(source, destination, next)
+                }
+                None => Err(e),
+            }
+    }
+}
+
+// Removes a file or (dangling) symlink, a missing file is not an error.
+pub fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(windows)]
+pub fn symlink_file(source: &Path, destination: &Path) -> io::Result<()> {
+    // Requires developer mode or elevated rights
+    std::os::windows::fs::symlink_file(source, destination)
+}
+
+#[cfg(unix)]
+pub fn symlink_file(source: &Path, destination: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, destination)
+}
+
+fn is_up_to_date(source: &Path, target: &Path) -> bool {
+    // A symlink to the source is always up to date
+    if let Ok(link) = fs::read_link(target) {
+        return link == source;
+    }
+
+    // Hard links share the metadata of the source, copies keep an older or equal modification time
+    match (fs::metadata(source), fs::metadata(target)) {
+        (Ok(source_meta), Ok(target_meta)) => {
+            if source_meta.len() != target_meta.len() {
+                return false;
+            }
+            match (source_meta.modified(), target_meta.modified()) {
+                (Ok(source_time), Ok(target_time)) => target_time >= source_time,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn remove_empty_parents(path: &Path, root: &Path) {
+    let mut current = path.parent();
+    while let Some(dir) = current {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+        // Fails if the directory is not empty, which ends the cleanup
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+fn load_manifest(path: &Path) -> StageManifest {
+    let mut contents = String::new();
+    match File::open(path).and_then(|mut file| file.read_to_string(&mut contents)) {
+        Ok(_) => serde_json::from_str(&contents).unwrap_or_default(),
+        Err(_) => StageManifest::default(),
+    }
+}
+
+fn save_manifest(path: &Path, manifest: &StageManifest) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(manifest)?;
+    let mut file = File::create(path)?;
+    file.write_all(contents.as_bytes())
+}
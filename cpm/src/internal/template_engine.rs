@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+// Minimal template language used by the project templates:
+//     {{name}}                          ---> value of the variable
+//     {{#if name}} ... {{else}} ... {{/if}} ---> rendered if the variable is set (not empty, 'false', '0' or 'OFF')
+//     {{#each name}} ... {{this}} ... {{/each}} ---> rendered for every comma separated item of the variable
+// Block tags that are alone on their line remove the whole line, so they do not leave blank lines.
+// Every value is a string, lists are comma separated strings, i.e. 'sources=a.cpp,b.cpp'.
+pub type Variables = BTreeMap<String, String>;
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Variable(String),
+    If(String),
+    Else,
+    EndIf,
+    Each(String),
+    EndEach,
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Variable(String),
+    If(String, Vec<Node>, Vec<Node>),
+    Each(String, Vec<Node>),
+}
+
+pub fn render(template: &str, variables: &Variables) -> Result<String, String> {
+    let tokens = tokenize(template)?;
+    let mut tokens = tokens.into_iter();
+    let (nodes, end) = parse(&mut tokens)?;
+    if let Some(end) = end {
+        return Err(format!("unexpected {}", describe(&end)));
+    }
+
+    let mut output = String::new();
+    render_nodes(&nodes, variables, None, &mut output)?;
+    Ok(output)
+}
+
+pub fn is_truthy(value: &str) -> bool {
+    let value = value.trim();
+    !(value.is_empty() ||
+        value.eq_ignore_ascii_case("false") ||
+        value == "0" ||
+        value.eq_ignore_ascii_case("off"))
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut rest = template;
+    // Whether 'rest' starts at the beginning of a line
+    let mut at_line_start = true;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            return Err("unclosed '{{'".to_string());
+        };
+        let tag = rest[start + 2..start + 2 + length].trim();
+        let mut text = rest[..start].to_string();
+        let mut after = &rest[start + 2 + length + 2..];
+        let mut standalone = false;
+
+        let token = if let Some(name) = tag.strip_prefix("#if ") {
+            Token::If(name.trim().to_string())
+        } else if let Some(name) = tag.strip_prefix("#each ") {
+            Token::Each(name.trim().to_string())
+        } else if tag == "else" {
+            Token::Else
+        } else if tag == "/if" {
+            Token::EndIf
+        } else if tag == "/each" {
+            Token::EndEach
+        } else if tag.starts_with('#') || tag.starts_with('/') {
+            return Err(format!("unknown tag '{{{{{}}}}}'", tag));
+        } else {
+            Token::Variable(tag.to_string())
+        };
+
+        // A block tag alone on its line takes the line with it
+        if !matches!(token, Token::Variable(_)) {
+            let line_start = text.rfind('\n').map(|i| i + 1);
+            let starts_line =
+                text[line_start.unwrap_or(0)..].trim().is_empty() &&
+                (line_start.is_some() || at_line_start);
+            let line_end = after.find('\n');
+            let ends_line = match line_end {
+                Some(end) => after[..end].trim().is_empty(),
+                None => after.trim().is_empty(),
+            };
+            if starts_line && ends_line {
+                text.truncate(line_start.unwrap_or(0));
+                after = match line_end {
+                    Some(end) => &after[end + 1..],
+                    None => "",
+                };
+                standalone = true;
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+        tokens.push(token);
+        rest = after;
+        at_line_start = standalone;
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+
+    Ok(tokens)
+}
+
+// Parses until a closing tag (returned) or the end of the tokens.
+fn parse(tokens: &mut impl Iterator<Item = Token>) -> Result<(Vec<Node>, Option<Token>), String> {
+    let mut nodes: Vec<Node> = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Variable(name) => nodes.push(Node::Variable(name)),
+            Token::If(name) => {
+                let (then, end) = parse(tokens)?;
+                let otherwise = match end {
+                    Some(Token::EndIf) => Vec::new(),
+                    Some(Token::Else) => {
+                        let (otherwise, end) = parse(tokens)?;
+                        if !matches!(end, Some(Token::EndIf)) {
+                            return Err(format!("missing '{{{{/if}}}}' for '{}'", name));
+                        }
+                        otherwise
+                    }
+                    _ => {
+                        return Err(format!("missing '{{{{/if}}}}' for '{}'", name));
+                    }
+                };
+                nodes.push(Node::If(name, then, otherwise));
+            }
+            Token::Each(name) => {
+                let (body, end) = parse(tokens)?;
+                if !matches!(end, Some(Token::EndEach)) {
+                    return Err(format!("missing '{{{{/each}}}}' for '{}'", name));
+                }
+                nodes.push(Node::Each(name, body));
+            }
+            Token::Else | Token::EndIf | Token::EndEach => {
+                return Ok((nodes, Some(token)));
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    variables: &Variables,
+    item: Option<&str>,
+    output: &mut String
+) -> Result<(), String> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(name) => output.push_str(&lookup(name, variables, item)?),
+            Node::If(name, then, otherwise) => {
+                let value = variables.get(name).map(|v| v.as_str()).unwrap_or("");
+                let branch = if is_truthy(value) { then } else { otherwise };
+                render_nodes(branch, variables, item, output)?;
+            }
+            Node::Each(name, body) => {
+                let value = lookup(name, variables, item)?;
+                for entry in value.split(',').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+                    render_nodes(body, variables, Some(entry), output)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn lookup(name: &str, variables: &Variables, item: Option<&str>) -> Result<String, String> {
+    if name == "this" {
+        return item
+            .map(|item| item.to_string())
+            .ok_or("'{{this}}' used outside of '{{#each}}'".to_string());
+    }
+    variables
+        .get(name)
+        .cloned()
+        .ok_or(format!("unknown variable '{}'", name))
+}
+
+fn describe(token: &Token) -> &'static str {
+    match token {
+        Token::Else => "'{{else}}'",
+        Token::EndIf => "'{{/if}}'",
+        Token::EndEach => "'{{/each}}'",
+        _ => "tag",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> Variables {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_variables() {
+        let variables = variables(&[("project_name", "app"), ("version", "1.0")]);
+        let rendered = render("project({{project_name}} VERSION {{ version }})\n", &variables);
+        assert_eq!(rendered.unwrap(), "project(app VERSION 1.0)\n");
+        assert_eq!(render("no tags { } }}", &variables).unwrap(), "no tags { } }}");
+    }
+
+    #[test]
+    fn renders_if_else_blocks() {
+        let template = "{{#if shared}}SHARED{{else}}STATIC{{/if}}";
+        let cases = [("ON", "SHARED"), ("OFF", "STATIC"), ("0", "STATIC"), ("", "STATIC")];
+        for (value, expected) in cases {
+            let rendered = render(template, &variables(&[("shared", value)])).unwrap();
+            assert_eq!(rendered, expected);
+        }
+        // An unset variable is false
+        assert_eq!(render(template, &Variables::new()).unwrap(), "STATIC");
+    }
+
+    #[test]
+    fn renders_nested_if_and_each_blocks() {
+        let template = "\
+add_executable(app)
+{{#if packages}}
+{{#each packages}}
+find_package({{this}} CONFIG REQUIRED)
+{{#if static}}
+set({{this}}_STATIC ON)
+{{/if}}
+{{/each}}
+{{/if}}
+end()
+";
+        let variables = variables(&[("packages", "fmt, spdlog,"), ("static", "ON")]);
+        assert_eq!(
+            render(template, &variables).unwrap(),
+            "add_executable(app)\n\
+             find_package(fmt CONFIG REQUIRED)\nset(fmt_STATIC ON)\n\
+             find_package(spdlog CONFIG REQUIRED)\nset(spdlog_STATIC ON)\n\
+             end()\n"
+        );
+
+        let variables = Variables::from([("packages".to_string(), String::new())]);
+        assert_eq!(render(template, &variables).unwrap(), "add_executable(app)\nend()\n");
+    }
+
+    #[test]
+    fn keeps_lines_of_inline_block_tags() {
+        let template = "a {{#if x}}b{{/if}}\n  {{#if x}}\nc\n  {{/if}}\n";
+        assert_eq!(render(template, &variables(&[("x", "1")])).unwrap(), "a b\nc\n");
+    }
+
+    #[test]
+    fn fails_on_missing_variables() {
+        let error = render("{{missing}}", &Variables::new()).unwrap_err();
+        assert!(error.contains("missing"));
+        assert!(render("{{#each missing}}{{/each}}", &Variables::new()).is_err());
+        assert!(render("{{this}}", &Variables::new()).is_err());
+    }
+
+    #[test]
+    fn fails_on_unclosed_or_mismatched_blocks() {
+        let variables = variables(&[("x", "1")]);
+        for template in [
+            "{{x",
+            "{{#if x}}open",
+            "{{#if x}}a{{else}}b",
+            "{{#each x}}open",
+            "{{#if x}}a{{/each}}",
+            "{{#each x}}a{{/if}}",
+            "{{#each x}}a{{else}}b{{/each}}",
+            "{{/if}}",
+            "{{else}}",
+            "{{#unless x}}{{/unless}}",
+        ] {
+            assert!(render(template, &variables).is_err(), "{}", template);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+use walkdir::WalkDir;
+
+use crate::internal::install::Presets;
+use crate::internal::project_root;
+use crate::internal::settings::Settings;
+use crate::internal::template_engine;
+
+pub use crate::internal::template_engine::Variables;
+
+// Project templates. A template is a directory whose file contents and file paths are rendered with
+// the template engine, i.e.
+//     include/{{project_name}}/{{project_name}}.hpp -> include/app/app.hpp
+// Templates are looked up by name in (first match wins):
+//     <project>/.cpm/templates/<template>/   ---> project-local templates
+//     <templates_dir>/<template>/            ---> user templates, defaults to '<exe_dir>/templates'
+//     presets/templates/<template>/          ---> embedded templates
+// An optional 'template.json' holds the description and the default values of the template variables:
+//     { "description": "...", "variables": { "version": "0.1.0" } }
+pub const TEMPLATES_DIR: &str = "templates";
+pub const PROJECT_TEMPLATES_DIR: &str = ".cpm/templates";
+pub const METADATA_FILE: &str = "template.json";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateSource {
+    Project,
+    User,
+    Embedded,
+}
+
+impl TemplateSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateSource::Project => "project",
+            TemplateSource::User => "user",
+            TemplateSource::Embedded => "embedded",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct TemplateMetadata {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub variables: Variables,
+}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub source: TemplateSource,
+    // Template directory, None for the embedded templates
+    pub root: Option<PathBuf>,
+    pub metadata: TemplateMetadata,
+    // Lower priority sources that have a template with the same name
+    pub overrides: Vec<TemplateSource>,
+}
+
+// Short forms of the embedded templates: (alias, template)
+const ALIASES: [(&str, &str); 5] = [
+    ("exe", "executable"),
+    ("static", "static-library"),
+    ("shared", "shared-library"),
+    ("header", "header-only"),
+    ("lib-tests", "library-tests"),
+];
+
+// Templates of the 'add' targets are rendered into a project, not as a project.
+pub const TARGET_TEMPLATE_PREFIX: &str = "add-";
+
+// Template directories in priority order.
+pub fn search_dirs(settings: &Settings) -> Vec<(TemplateSource, PathBuf)> {
+    let mut dirs: Vec<(TemplateSource, PathBuf)> = Vec::new();
+
+    let project_root = if settings.initialized && !settings.working_dir.is_empty() {
+        PathBuf::from(&settings.working_dir)
+    } else {
+        project_root::current().unwrap_or_default()
+    };
+    dirs.push((TemplateSource::Project, project_root.join(PROJECT_TEMPLATES_DIR)));
+
+    let user_dir = if settings.templates_dir.is_empty() {
+        Path::new(&settings.exe_dir).join(TEMPLATES_DIR)
+    } else {
+        PathBuf::from(&settings.templates_dir)
+    };
+    dirs.push((TemplateSource::User, user_dir));
+
+    dirs
+}
+
+// Every available template, sorted by name. Overridden templates are only listed once.
+pub fn list(settings: &Settings) -> Vec<Template> {
+    let mut templates: Vec<Template> = Vec::new();
+
+    let mut add = |template: Template| {
+        match templates.iter_mut().find(|t| t.name == template.name) {
+            Some(existing) => existing.overrides.push(template.source),
+            None => templates.push(template),
+        }
+    };
+
+    for (source, dir) in search_dirs(settings) {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        entries.sort();
+
+        for root in entries {
+            let name = root.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let metadata = fs
+                ::read_to_string(root.join(METADATA_FILE))
+                .ok()
+                .and_then(|contents| serde_json::from_str(&contents).ok())
+                .unwrap_or_default();
+            add(Template { name, source, root: Some(root), metadata, overrides: Vec::new() });
+        }
+    }
+
+    let prefix = format!("{}/", TEMPLATES_DIR);
+    let mut embedded: Vec<String> = Presets::iter()
+        .filter_map(|file| {
+            file.strip_prefix(&prefix)
+                .and_then(|rest| rest.split_once('/'))
+                .map(|(name, _)| name.to_string())
+        })
+        .collect();
+    embedded.sort();
+    embedded.dedup();
+    for name in embedded {
+        let metadata = Presets::get(&format!("{}{}/{}", prefix, name, METADATA_FILE))
+            .and_then(|file| serde_json::from_slice(&file.data).ok())
+            .unwrap_or_default();
+        add(Template {
+            name,
+            source: TemplateSource::Embedded,
+            root: None,
+            metadata,
+            overrides: Vec::new(),
+        });
+    }
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+// Templates available to 'new', without the 'add-*' target templates.
+pub fn project_templates(settings: &Settings) -> Vec<Template> {
+    list(settings)
+        .into_iter()
+        .filter(|template| !is_target_template(&template.name))
+        .collect()
+}
+
+pub fn is_target_template(name: &str) -> bool {
+    name.starts_with(TARGET_TEMPLATE_PREFIX)
+}
+
+// Finds a project template by name or alias.
+pub fn find(settings: &Settings, name: &str) -> Option<Template> {
+    let templates = project_templates(settings);
+
+    if let Some(template) = templates.iter().find(|t| t.name == name) {
+        return Some(template.clone());
+    }
+
+    let (_, template_name) = ALIASES.iter().find(|(alias, _)| alias.eq_ignore_ascii_case(name))?;
+    templates.into_iter().find(|t| t.name == *template_name)
+}
+
+// Finds an 'add-*' target template by name.
+pub fn find_target(settings: &Settings, name: &str) -> Option<Template> {
+    list(settings)
+        .into_iter()
+        .find(|template| is_target_template(&template.name) && template.name == name)
+}
+
+// Parses 'KEY=VALUE' definitions (-D), returns the first invalid one as the error.
+pub fn parse_defines(defines: &[String]) -> Result<Variables, String> {
+    let mut variables = Variables::new();
+    for define in defines {
+        match define.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                variables.insert(key.trim().to_string(), value.to_string());
+            }
+            _ => {
+                return Err(define.clone());
+            }
+        }
+    }
+    Ok(variables)
+}
+
+// Template defaults, then the project variables, then the user defined ones.
+pub fn variables(
+    template: &Template,
+    project_name: &str,
+    cxx_standard: &str,
+    defines: &Variables
+) -> Variables {
+    let mut variables = template.metadata.variables.clone();
+    variables.insert("project_name".to_string(), project_name.to_string());
+    variables.insert("project_identifier".to_string(), identifier(project_name));
+    variables.insert("cxx_standard".to_string(), cxx_standard.to_string());
+    variables.extend(defines.iter().map(|(key, value)| (key.clone(), value.clone())));
+    variables
+}
+
+// Variables of the 'add-*' target templates, see 'add'.
+pub fn target_variables(template: &Template, target_name: &str, defines: &Variables) -> Variables {
+    let mut variables = template.metadata.variables.clone();
+    variables.insert("target_name".to_string(), target_name.to_string());
+    variables.insert("target_identifier".to_string(), identifier(target_name));
+    variables.extend(defines.iter().map(|(key, value)| (key.clone(), value.clone())));
+    variables
+}
+
+// Renders the template into the destination. Existing files are only replaced with 'force'.
+// Returns the written and the skipped files.
+pub fn render(
+    template: &Template,
+    variables: &Variables,
+    destination: &Path,
+    force: bool
+) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut skipped: Vec<PathBuf> = Vec::new();
+
+    for (file, data) in files(template)? {
+        let relative = render_text(&file, &file, variables)?;
+        let path = destination.join(relative);
+
+        if path.exists() && !force {
+            skipped.push(path);
+            continue;
+        }
+
+        // Non UTF-8 files (images, ...) are copied as they are
+        let contents = match String::from_utf8(data) {
+            Ok(text) => render_text(&file, &text, variables)?.into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+
+    Ok((written, skipped))
+}
+
+// (relative path with '/' separators, contents) of the template files, without the metadata file.
+fn files(template: &Template) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+    match &template.root {
+        Some(root) => {
+            for entry in WalkDir::new(root).sort_by_file_name() {
+                let entry = entry.map_err(io::Error::other)?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let relative = entry
+                    .path()
+                    .strip_prefix(root)
+                    .unwrap_or(entry.path())
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                if relative != METADATA_FILE {
+                    files.push((relative, fs::read(entry.path())?));
+                }
+            }
+        }
+        None => {
+            let prefix = format!("{}/{}/", TEMPLATES_DIR, template.name);
+            for file in Presets::iter().filter(|file| file.starts_with(&prefix)) {
+                let relative = &file[prefix.len()..];
+                if relative == METADATA_FILE {
+                    continue;
+                }
+                if let Some(embedded) = Presets::get(&file) {
+                    files.push((relative.to_string(), embedded.data.into_owned()));
+                }
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+fn render_text(file: &str, text: &str, variables: &Variables) -> io::Result<String> {
+    template_engine
+        ::render(text, variables)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", file, e)))
+}
+
+// Project names may contain '-' which is not valid in C++ identifiers (namespaces).
+fn identifier(name: &str) -> String {
+    let identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", identifier)
+    } else {
+        identifier
+    }
+}
@@ -0,0 +1,222 @@
+use std::path::{ Path, PathBuf };
+
+// Build variants instrument the whole project with sanitizers and/or coverage. Each variant is
+// generated into its own build tree '<build_dir>/<variant>' so it never invalidates the plain build.
+// The flags are passed as CMAKE_<LANG>_FLAGS_INIT and CMAKE_<KIND>_LINKER_FLAGS_INIT, which only
+// initialize the cache of a fresh build tree and keep the compiler defaults (i.e. /EHsc on MSVC).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sanitizer {
+    Address,
+    Undefined,
+    Thread,
+    Memory,
+    Leak,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Variant {
+    pub sanitizers: Vec<Sanitizer>,
+    pub coverage: bool,
+}
+
+impl Sanitizer {
+    pub fn parse(sanitizer: &str) -> Option<Self> {
+        match sanitizer.trim().to_lowercase().as_str() {
+            "address" | "asan" => Some(Sanitizer::Address),
+            "undefined" | "ubsan" => Some(Sanitizer::Undefined),
+            "thread" | "tsan" => Some(Sanitizer::Thread),
+            "memory" | "msan" => Some(Sanitizer::Memory),
+            "leak" | "lsan" => Some(Sanitizer::Leak),
+            _ => None,
+        }
+    }
+
+    // Name used by -fsanitize=
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sanitizer::Address => "address",
+            Sanitizer::Undefined => "undefined",
+            Sanitizer::Thread => "thread",
+            Sanitizer::Memory => "memory",
+            Sanitizer::Leak => "leak",
+        }
+    }
+
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Sanitizer::Address => "asan",
+            Sanitizer::Undefined => "ubsan",
+            Sanitizer::Thread => "tsan",
+            Sanitizer::Memory => "msan",
+            Sanitizer::Leak => "lsan",
+        }
+    }
+
+    fn supported_by(&self, system_type: &str) -> bool {
+        match system_type {
+            "unix/clang" => true,
+            // MemorySanitizer is Clang only
+            "unix/gcc" => *self != Sanitizer::Memory,
+            "nt/msvc" => *self == Sanitizer::Address,
+            _ => false,
+        }
+    }
+}
+
+impl Variant {
+    // Parses the comma separated '--sanitize' list.
+    pub fn parse(sanitize: Option<&str>, coverage: bool) -> Result<Self, String> {
+        let mut variant = Variant { sanitizers: Vec::new(), coverage };
+        if let Some(sanitize) = sanitize {
+            for name in sanitize.split(',').filter(|name| !name.trim().is_empty()) {
+                match Sanitizer::parse(name) {
+                    Some(sanitizer) => {
+                        if !variant.sanitizers.contains(&sanitizer) {
+                            variant.sanitizers.push(sanitizer);
+                        }
+                    }
+                    None => {
+                        return Err(format!("unknown sanitizer '{}'", name.trim()));
+                    }
+                }
+            }
+        }
+        Ok(variant)
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.sanitizers.is_empty() && !self.coverage
+    }
+
+    // i.e. 'asan-ubsan-coverage', empty for the default variant
+    pub fn name(&self) -> String {
+        let mut parts: Vec<&str> = self.sanitizers
+            .iter()
+            .map(|s| s.short_name())
+            .collect();
+        if self.coverage {
+            parts.push("coverage");
+        }
+        parts.join("-")
+    }
+
+    // Returns the reason if the variant can not be built with the system and build type.
+    pub fn validate(&self, system_type: &str, build_type: &str) -> Result<(), String> {
+        for sanitizer in &self.sanitizers {
+            if !sanitizer.supported_by(system_type) {
+                return Err(
+                    format!("the {} sanitizer is not supported by '{}'", sanitizer.name(), system_type)
+                );
+            }
+        }
+
+        // Thread and memory sanitizers need their own runtime and can not be combined
+        for exclusive in [Sanitizer::Thread, Sanitizer::Memory] {
+            if self.sanitizers.contains(&exclusive) && self.sanitizers.len() > 1 {
+                return Err(
+                    format!("the {} sanitizer can not be combined with other sanitizers", exclusive.name())
+                );
+            }
+        }
+
+        if system_type == "nt/msvc" {
+            if self.coverage {
+                return Err("coverage is not supported by 'nt/msvc'".to_string());
+            }
+            // The default Debug flags contain /RTC1 which MSVC refuses to combine with /fsanitize=address
+            if !self.sanitizers.is_empty() && build_type == "Debug" {
+                return Err(
+                    "the address sanitizer requires a Release build with 'nt/msvc' (conflicts with /RTC1)".to_string()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure definitions for the variant: (compile flags, linker flags).
+    pub fn flags(&self, system_type: &str) -> (Vec<String>, Vec<String>) {
+        let mut compile_flags: Vec<String> = Vec::new();
+        let mut link_flags: Vec<String> = Vec::new();
+
+        let names: Vec<&str> = self.sanitizers
+            .iter()
+            .map(|s| s.name())
+            .collect();
+
+        match system_type {
+            "nt/msvc" => {
+                if !names.is_empty() {
+                    compile_flags.push(format!("/fsanitize={}", names.join(",")));
+                    compile_flags.push("/Zi".to_string());
+                    link_flags.push("/INCREMENTAL:NO".to_string());
+                }
+            }
+            _ => {
+                if !names.is_empty() {
+                    let sanitize = format!("-fsanitize={}", names.join(","));
+                    compile_flags.push(sanitize.clone());
+                    compile_flags.push("-fno-omit-frame-pointer".to_string());
+                    compile_flags.push("-g".to_string());
+                    link_flags.push(sanitize);
+                }
+                if self.coverage {
+                    if system_type == "unix/clang" {
+                        // Source based coverage, read with llvm-profdata and llvm-cov
+                        compile_flags.push("-fprofile-instr-generate".to_string());
+                        compile_flags.push("-fcoverage-mapping".to_string());
+                        link_flags.push("-fprofile-instr-generate".to_string());
+                    } else {
+                        // gcov notes and data files, read with gcov
+                        compile_flags.push("--coverage".to_string());
+                        link_flags.push("--coverage".to_string());
+                    }
+                }
+            }
+        }
+
+        (compile_flags, link_flags)
+    }
+
+    // -D arguments for the configure command.
+    pub fn definitions(&self, system_type: &str) -> Vec<String> {
+        if self.is_default() {
+            return vec![];
+        }
+
+        let (compile_flags, link_flags) = self.flags(system_type);
+        let compile_flags = compile_flags.join(" ");
+        let link_flags = link_flags.join(" ");
+
+        let mut definitions: Vec<String> = Vec::new();
+        for language in ["C", "CXX"] {
+            definitions.push(format!("-DCMAKE_{}_FLAGS_INIT={}", language, compile_flags));
+        }
+        if !link_flags.is_empty() {
+            for kind in ["EXE", "SHARED", "MODULE"] {
+                definitions.push(format!("-DCMAKE_{}_LINKER_FLAGS_INIT={}", kind, link_flags));
+            }
+        }
+        definitions
+    }
+}
+
+// The plain build tree, independent of the currently selected variant.
+pub fn base_build_dir(build_dir: &str, current_variant: &str) -> PathBuf {
+    let build_dir = Path::new(build_dir);
+    let in_variant_dir =
+        !current_variant.is_empty() &&
+        build_dir.file_name().is_some_and(|name| name == current_variant);
+    match build_dir.parent() {
+        Some(parent) if in_variant_dir => parent.to_path_buf(),
+        _ => build_dir.to_path_buf(),
+    }
+}
+
+pub fn variant_build_dir(base_build_dir: &Path, variant: &Variant) -> PathBuf {
+    if variant.is_default() {
+        base_build_dir.to_path_buf()
+    } else {
+        base_build_dir.join(variant.name())
+    }
+}
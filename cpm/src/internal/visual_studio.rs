@@ -0,0 +1,240 @@
+use serde_json::{ json, Map, Value };
+use std::path::Path;
+
+use crate::internal::ide_profile::{ self, CMakeProfile };
+use crate::internal::vscode;
+
+// Visual Studio 'Open Folder' files at the project root:
+//     CMakeSettings.json  ---> a configuration per CMake profile
+//     launch.vs.json      ---> debug targets of the executable targets
+// Entries named 'cpm-...' are replaced on every run, other entries are kept.
+pub const CMAKE_SETTINGS_FILE: &str = "CMakeSettings.json";
+pub const LAUNCH_FILE: &str = "launch.vs.json";
+
+// Executable target for launch.vs.json.
+#[derive(Debug, Clone)]
+pub struct LaunchTarget {
+    pub name: String,
+    // File name and path relative to the build root, i.e. 'app.exe' and 'src\app.exe'
+    pub file_name: String,
+    pub relative_path: String,
+    // Runtime library directories, '${cmake.buildRoot}\...' or absolute
+    pub library_dirs: Vec<String>,
+}
+
+// Path inside the project as '${projectDir}\...', other paths as they are. Always with '\'.
+pub fn project_path(project_root: &Path, path: &Path) -> String {
+    match path.strip_prefix(project_root) {
+        Ok(relative) if relative.as_os_str().is_empty() => "${projectDir}".to_string(),
+        Ok(relative) => {
+            format!("${{projectDir}}\\{}", relative.to_string_lossy().replace('/', "\\"))
+        }
+        Err(_) => path.to_string_lossy().replace('/', "\\"),
+    }
+}
+
+pub fn cmake_settings_configurations(
+    profiles: &[CMakeProfile],
+    project_root: &Path
+) -> Vec<Value> {
+    profiles
+        .iter()
+        .map(|profile| {
+            let variables: Vec<Value> = profile.definitions
+                .iter()
+                .map(|definition| {
+                    let mut variable = Map::new();
+                    variable.insert("name".to_string(), json!(definition.key));
+                    variable.insert("value".to_string(), json!(definition.value));
+                    if let Some(value_type) = &definition.value_type {
+                        variable.insert("type".to_string(), json!(value_type));
+                    }
+                    Value::Object(variable)
+                })
+                .collect();
+
+            let mut configuration = Map::new();
+            configuration.insert("name".to_string(), json!(profile.name()));
+            configuration.insert("generator".to_string(), json!(profile.generator));
+            configuration.insert("configurationType".to_string(), json!(profile.build_type));
+            // GCC comes from the PATH, MSVC and Clang (MSVC ABI) need the developer environment
+            if profile.system_type != "unix/gcc" {
+                configuration.insert("inheritEnvironments".to_string(), json!(["msvc_x64_x64"]));
+            }
+            configuration.insert(
+                "buildRoot".to_string(),
+                json!(project_path(project_root, &profile.build_dir))
+            );
+            configuration.insert(
+                "installRoot".to_string(),
+                json!(project_path(project_root, &profile.install_dir))
+            );
+            configuration.insert("cmakeCommandArgs".to_string(), json!(""));
+            configuration.insert("buildCommandArgs".to_string(), json!(""));
+            configuration.insert("ctestCommandArgs".to_string(), json!(""));
+            configuration.insert("variables".to_string(), json!(variables));
+            Value::Object(configuration)
+        })
+        .collect()
+}
+
+// Targets are matched by Visual Studio as '<file name> (<path relative to the build root>)'.
+pub fn launch_configurations(targets: &[LaunchTarget]) -> Vec<Value> {
+    targets
+        .iter()
+        .map(|target| {
+            let mut configuration = Map::new();
+            configuration.insert("type".to_string(), json!("default"));
+            configuration.insert("project".to_string(), json!("CMakeLists.txt"));
+            configuration.insert(
+                "projectTarget".to_string(),
+                json!(format!("{} ({})", target.file_name, target.relative_path))
+            );
+            configuration.insert(
+                "name".to_string(),
+                json!(format!("{}{}", ide_profile::PREFIX, target.name))
+            );
+            configuration.insert("args".to_string(), json!([]));
+            if !target.library_dirs.is_empty() {
+                let path = format!("{};${{env.PATH}}", target.library_dirs.join(";"));
+                configuration.insert("env".to_string(), json!({ "PATH": path }));
+            }
+            Value::Object(configuration)
+        })
+        .collect()
+}
+
+pub fn merge_cmake_settings(document: Value, configurations: Vec<Value>) -> Value {
+    let is_generated = ide_profile::is_generated;
+    vscode::merge_entries(document, "configurations", "name", is_generated, configurations)
+}
+
+pub fn merge_launch(document: Value, configurations: Vec<Value>) -> Value {
+    let mut document = match document {
+        Value::Object(document) => document,
+        _ => Map::new(),
+    };
+    document.entry("version").or_insert(json!("0.2.1"));
+    document.entry("defaults").or_insert(json!({}));
+    vscode::merge_entries(
+        Value::Object(document),
+        "configurations",
+        "name",
+        ide_profile::is_generated,
+        configurations
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::configure_command::Definition;
+    use std::path::PathBuf;
+
+    fn profile(system_type: &str) -> CMakeProfile {
+        CMakeProfile {
+            id: format!("{}-Release", ide_profile::compiler_name(system_type)),
+            system_type: system_type.to_string(),
+            build_type: "Release".to_string(),
+            generator: ide_profile::GENERATOR.to_string(),
+            definitions: ["CMAKE_TOOLCHAIN_FILE:FILEPATH=C:/vcpkg/vcpkg.cmake", "X=1"]
+                .iter()
+                .filter_map(|definition| Definition::parse(definition))
+                .collect(),
+            build_dir: PathBuf::from("C:/project/Build/ide/msvc-Release"),
+            install_dir: PathBuf::from("D:/install"),
+        }
+    }
+
+    #[test]
+    fn writes_a_configuration_per_profile() {
+        let root = PathBuf::from("C:/project");
+        let configurations = cmake_settings_configurations(&[profile("nt/msvc")], &root);
+        assert_eq!(
+            configurations[0],
+            json!({
+                "name": "cpm-msvc-Release",
+                "generator": "Ninja",
+                "configurationType": "Release",
+                "inheritEnvironments": ["msvc_x64_x64"],
+                "buildRoot": "${projectDir}\\Build\\ide\\msvc-Release",
+                "installRoot": "D:\\install",
+                "cmakeCommandArgs": "",
+                "buildCommandArgs": "",
+                "ctestCommandArgs": "",
+                "variables": [
+                    {
+                        "name": "CMAKE_TOOLCHAIN_FILE",
+                        "value": "C:/vcpkg/vcpkg.cmake",
+                        "type": "FILEPATH"
+                    },
+                    { "name": "X", "value": "1" }
+                ]
+            })
+        );
+
+        // GCC does not need the developer environment
+        let configurations = cmake_settings_configurations(&[profile("unix/gcc")], &root);
+        assert!(configurations[0].get("inheritEnvironments").is_none());
+        assert_eq!(project_path(&root, &root), "${projectDir}");
+    }
+
+    #[test]
+    fn replaces_generated_configurations_and_keeps_user_ones() {
+        let document = json!({
+            "environments": [{ "KEEP": "1" }],
+            "configurations": [
+                { "name": "x64-Debug" },
+                { "name": "cpm-old-Debug" },
+                { "name": "cpm-msvc-Release", "generator": "Visual Studio 17 2022" }
+            ]
+        });
+        let root = PathBuf::from("C:/project");
+        let configurations = cmake_settings_configurations(&[profile("nt/msvc")], &root);
+        let merged = merge_cmake_settings(document, configurations);
+
+        let names: Vec<&str> = merged["configurations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|configuration| configuration["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["x64-Debug", "cpm-msvc-Release"]);
+        assert_eq!(merged["configurations"][1]["generator"], "Ninja");
+        assert_eq!(merged["environments"], json!([{ "KEEP": "1" }]));
+    }
+
+    #[test]
+    fn merges_launch_targets() {
+        let target = LaunchTarget {
+            name: "app".to_string(),
+            file_name: "app.exe".to_string(),
+            relative_path: "src\\app.exe".to_string(),
+            library_dirs: vec!["${cmake.buildRoot}\\lib".to_string()],
+        };
+        let document = json!({
+            "version": "0.2.1",
+            "defaults": { "user": true },
+            "configurations": [{ "name": "mine" }, { "name": "cpm-app" }]
+        });
+        let merged = merge_launch(document, launch_configurations(&[target]));
+        assert_eq!(merged["defaults"], json!({ "user": true }));
+        assert_eq!(merged["configurations"][0], json!({ "name": "mine" }));
+        assert_eq!(
+            merged["configurations"][1],
+            json!({
+                "type": "default",
+                "project": "CMakeLists.txt",
+                "projectTarget": "app.exe (src\\app.exe)",
+                "name": "cpm-app",
+                "args": [],
+                "env": { "PATH": "${cmake.buildRoot}\\lib;${env.PATH}" }
+            })
+        );
+        assert_eq!(merged["configurations"].as_array().unwrap().len(), 2);
+
+        // A new file gets the version and the defaults
+        let merged = merge_launch(Value::Null, Vec::new());
+        assert_eq!(merged, json!({ "version": "0.2.1", "defaults": {}, "configurations": [] }));
+    }
+}
@@ -0,0 +1,377 @@
+use serde_json::{ json, Map, Value };
+use std::path::Path;
+
+// VS Code workspace files in '<project>/.vscode/':
+//     tasks.json              ---> configure/build/test/install tasks running CPM
+//     launch.json             ---> debug configurations of the executable targets
+//     c_cpp_properties.json   ---> 'CPM' configuration of the C/C++ extension
+//     settings.json           ---> clangd arguments
+// Entries generated by CPM are recognized by the 'cpm: ' prefix of their label/name (the 'CPM'
+// configuration for c_cpp_properties.json). They are replaced on every run, other entries are kept.
+pub const VSCODE_DIR: &str = ".vscode";
+pub const PREFIX: &str = "cpm: ";
+pub const BUILD_TYPES: [&str; 2] = ["Debug", "Release"];
+const CPP_CONFIGURATION: &str = "CPM";
+const COMPILE_COMMANDS_DIR_ARGUMENT: &str = "--compile-commands-dir=";
+
+// Executable target of a configuration, for launch.json.
+#[derive(Debug, Clone)]
+pub struct Executable {
+    pub name: String,
+    pub build_type: String,
+    // Workspace path of the artifact
+    pub program: String,
+    // Runtime library search path (variable, value), empty when not needed
+    pub environment: Vec<(String, String)>,
+}
+
+// Path inside the project as '${workspaceFolder}/...', other paths as they are. Always with '/'.
+pub fn workspace_path(project_root: &Path, path: &Path) -> String {
+    match path.strip_prefix(project_root) {
+        Ok(relative) if relative.as_os_str().is_empty() => "${workspaceFolder}".to_string(),
+        Ok(relative) => {
+            format!("${{workspaceFolder}}/{}", relative.to_string_lossy().replace('\\', "/"))
+        }
+        Err(_) => path.to_string_lossy().replace('\\', "/"),
+    }
+}
+
+pub fn task_label(task: &str, build_type: &str) -> String {
+    format!("{}{} ({})", PREFIX, task, build_type)
+}
+
+// Tasks run the CPM executable directly ('process'), so they do not depend on the terminal's shell.
+pub fn tasks(program: &str, system_type: &str, default_build_type: &str) -> Vec<Value> {
+    let problem_matcher = if system_type == "nt/msvc" { "$msvc" } else { "$gcc" };
+    let mut tasks: Vec<Value> = Vec::new();
+
+    for build_type in BUILD_TYPES {
+        let flag = if build_type == "Debug" { "-d" } else { "-r" };
+        let is_default = build_type == default_build_type;
+
+        let mut configure = vec!["build", flag, "-g"];
+        if !system_type.is_empty() {
+            configure.push(system_type);
+        }
+        let steps = [
+            ("configure", configure, Value::Null, Value::Null),
+            (
+                "build",
+                vec!["build", flag, "-b"],
+                json!({ "kind": "build", "isDefault": is_default }),
+                Value::Null,
+            ),
+            (
+                "test",
+                vec!["test", flag],
+                json!({ "kind": "test", "isDefault": is_default }),
+                json!(task_label("build", build_type)),
+            ),
+            ("install", vec!["build", flag, "-i"], Value::Null, Value::Null),
+        ];
+
+        for (task, arguments, group, depends_on) in steps {
+            let mut args = vec!["--no-init"];
+            args.extend(arguments);
+
+            let mut entry = Map::new();
+            entry.insert("label".to_string(), json!(task_label(task, build_type)));
+            entry.insert("type".to_string(), json!("process"));
+            entry.insert("command".to_string(), json!(program));
+            entry.insert("args".to_string(), json!(args));
+            entry.insert("options".to_string(), json!({ "cwd": "${workspaceFolder}" }));
+            entry.insert("problemMatcher".to_string(), json!([problem_matcher]));
+            if !group.is_null() {
+                entry.insert("group".to_string(), group);
+            }
+            if !depends_on.is_null() {
+                entry.insert("dependsOn".to_string(), depends_on);
+            }
+            tasks.push(Value::Object(entry));
+        }
+    }
+
+    tasks
+}
+
+// MSVC builds are debugged with the Visual Studio debugger, GCC builds with gdb and Clang builds with lldb.
+pub fn launch_configurations(executables: &[Executable], system_type: &str) -> Vec<Value> {
+    executables
+        .iter()
+        .map(|executable| {
+            let mut entry = Map::new();
+            entry.insert(
+                "name".to_string(),
+                json!(format!("{}{} ({})", PREFIX, executable.name, executable.build_type))
+            );
+            entry.insert("request".to_string(), json!("launch"));
+            match system_type {
+                "nt/msvc" => {
+                    entry.insert("type".to_string(), json!("cppvsdbg"));
+                }
+                "unix/clang" => {
+                    entry.insert("type".to_string(), json!("cppdbg"));
+                    entry.insert("MIMode".to_string(), json!("lldb"));
+                }
+                _ => {
+                    entry.insert("type".to_string(), json!("cppdbg"));
+                    entry.insert("MIMode".to_string(), json!("gdb"));
+                }
+            }
+            entry.insert("program".to_string(), json!(executable.program));
+            entry.insert("args".to_string(), json!([]));
+            entry.insert("cwd".to_string(), json!("${workspaceFolder}"));
+            let environment: Vec<Value> = executable.environment
+                .iter()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect();
+            entry.insert("environment".to_string(), json!(environment));
+            if BUILD_TYPES.contains(&executable.build_type.as_str()) {
+                entry.insert(
+                    "preLaunchTask".to_string(),
+                    json!(task_label("build", &executable.build_type))
+                );
+            }
+            Value::Object(entry)
+        })
+        .collect()
+}
+
+// The compile database exported by 'cpm build' to the project root. The Visual Studio generator
+// does not write one, so MSVC projects only get the include path.
+pub fn cpp_configuration(system_type: &str, os: &str) -> Value {
+    let platform = match os {
+        "windows" => "windows",
+        "macos" => "macos",
+        _ => "linux",
+    };
+    let compiler = match system_type {
+        "nt/msvc" => "msvc",
+        "unix/clang" => "clang",
+        _ => "gcc",
+    };
+
+    let mut configuration = Map::new();
+    configuration.insert("name".to_string(), json!(CPP_CONFIGURATION));
+    configuration.insert("includePath".to_string(), json!(["${workspaceFolder}/**"]));
+    configuration.insert(
+        "intelliSenseMode".to_string(),
+        json!(format!("{}-{}-x64", platform, compiler))
+    );
+    if system_type != "nt/msvc" {
+        configuration.insert(
+            "compileCommands".to_string(),
+            json!("${workspaceFolder}/compile_commands.json")
+        );
+    }
+    Value::Object(configuration)
+}
+
+// Replaces the generated entries of the array under the key, keeps the others and their order.
+pub fn merge_entries(
+    document: Value,
+    key: &str,
+    name_key: &str,
+    is_generated: impl Fn(&str) -> bool,
+    generated: Vec<Value>
+) -> Value {
+    let mut document = match document {
+        Value::Object(document) => document,
+        _ => Map::new(),
+    };
+
+    // Inserting an existing key keeps its position, removing it would not
+    let mut entries: Vec<Value> = match document.get(key) {
+        Some(Value::Array(entries)) => entries.clone(),
+        _ => Vec::new(),
+    };
+    entries.retain(|entry| {
+        let name = entry.get(name_key).and_then(|name| name.as_str()).unwrap_or("");
+        !is_generated(name)
+    });
+    entries.extend(generated);
+
+    document.insert(key.to_string(), Value::Array(entries));
+    Value::Object(document)
+}
+
+pub fn merge_tasks(document: Value, tasks: Vec<Value>) -> Value {
+    let document = with_version(document, json!("2.0.0"));
+    merge_entries(document, "tasks", "label", |label| label.starts_with(PREFIX), tasks)
+}
+
+pub fn merge_launch(document: Value, configurations: Vec<Value>) -> Value {
+    let document = with_version(document, json!("0.2.0"));
+    let is_generated = |name: &str| name.starts_with(PREFIX);
+    merge_entries(document, "configurations", "name", is_generated, configurations)
+}
+
+pub fn merge_cpp_properties(document: Value, configuration: Value) -> Value {
+    let document = with_version(document, json!(4));
+    let is_generated = |name: &str| name == CPP_CONFIGURATION;
+    merge_entries(document, "configurations", "name", is_generated, vec![configuration])
+}
+
+// Points clangd at the project root, where 'cpm build' exports compile_commands.json.
+// The other clangd arguments are kept.
+pub fn merge_settings(document: Value) -> Value {
+    let mut document = match document {
+        Value::Object(document) => document,
+        _ => Map::new(),
+    };
+
+    let mut arguments: Vec<Value> = match document.get("clangd.arguments") {
+        Some(Value::Array(arguments)) => arguments.clone(),
+        _ => Vec::new(),
+    };
+    arguments.retain(|argument| {
+        !argument
+            .as_str()
+            .map(|argument| argument.starts_with(COMPILE_COMMANDS_DIR_ARGUMENT))
+            .unwrap_or(false)
+    });
+    arguments.push(json!(format!("{}${{workspaceFolder}}", COMPILE_COMMANDS_DIR_ARGUMENT)));
+
+    document.insert("clangd.arguments".to_string(), Value::Array(arguments));
+    Value::Object(document)
+}
+
+// VS Code files are JSON with comments and trailing commas. Both are removed before parsing,
+// so files with comments are only regenerated on request.
+pub fn strip_comments(contents: &str) -> String {
+    let chars: Vec<char> = contents.chars().collect();
+    let mut stripped: Vec<char> = Vec::with_capacity(chars.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('"', _) => {
+                let end = string_end(&chars, i);
+                stripped.extend(&chars[i..end]);
+                i = end;
+            }
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i = (i + 2).min(chars.len());
+            }
+            (c, _) => {
+                stripped.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    // A trailing comma is followed by the closing bracket, once the comments are gone
+    let mut json = String::with_capacity(stripped.len());
+    let mut i = 0;
+    while i < stripped.len() {
+        match stripped[i] {
+            '"' => {
+                let end = string_end(&stripped, i);
+                json.extend(&stripped[i..end]);
+                i = end;
+            }
+            ',' => {
+                let next = stripped[i + 1..].iter().find(|c| !c.is_whitespace());
+                if !matches!(next, Some('}') | Some(']')) {
+                    json.push(',');
+                }
+                i += 1;
+            }
+            c => {
+                json.push(c);
+                i += 1;
+            }
+        }
+    }
+    json
+}
+
+// True if the file has '//' or '/* */' comments outside of strings.
+pub fn has_comments(contents: &str) -> bool {
+    let chars: Vec<char> = contents.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('"', _) => {
+                i = string_end(&chars, i);
+            }
+            ('/', Some('/')) | ('/', Some('*')) => {
+                return true;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+    false
+}
+
+// i points at the opening quote. Returns the offset after the closing one.
+fn string_end(chars: &[char], i: usize) -> usize {
+    let mut j = i + 1;
+    while j < chars.len() && chars[j] != '"' {
+        j += if chars[j] == '\\' { 2 } else { 1 };
+    }
+    (j + 1).min(chars.len())
+}
+
+fn with_version(document: Value, version: Value) -> Value {
+    let mut document = match document {
+        Value::Object(document) => document,
+        _ => Map::new(),
+    };
+    document.entry("version").or_insert(version);
+    Value::Object(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_line_and_block_comments() {
+        let contents = "{\n  // line comment\n  \"a\": 1, /* block\n  comment */ \"b\": 2\n}\n";
+        let value: Value = serde_json::from_str(&strip_comments(contents)).unwrap();
+        assert_eq!(value, serde_json::json!({ "a": 1, "b": 2 }));
+    }
+
+    #[test]
+    fn keeps_comment_markers_inside_strings() {
+        let contents = r#"{ "url": "https://example.com/*path*/", "escaped": "a \" // b" } // c"#;
+        let value: Value = serde_json::from_str(&strip_comments(contents)).unwrap();
+        assert_eq!(value["url"], "https://example.com/*path*/");
+        assert_eq!(value["escaped"], "a \" // b");
+    }
+
+    #[test]
+    fn removes_trailing_commas() {
+        let contents = "{\n  \"list\": [1, 2, // two\n  ],\n  \"text\": \",]\",\n}";
+        let value: Value = serde_json::from_str(&strip_comments(contents)).unwrap();
+        assert_eq!(value, serde_json::json!({ "list": [1, 2], "text": ",]" }));
+    }
+
+    #[test]
+    fn strips_crlf_and_unclosed_comments() {
+        let contents = "{\r\n  \"a\": 1 // one\r\n}\r\n/* unclosed";
+        let value: Value = serde_json::from_str(&strip_comments(contents)).unwrap();
+        assert_eq!(value, serde_json::json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn detects_comments_outside_of_strings() {
+        assert!(has_comments("{ \"a\": 1 } // note"));
+        assert!(has_comments("{ /* note */ \"a\": 1 }"));
+        assert!(!has_comments(r#"{ "url": "https://example.com", "glob": "src/**/*.cpp" }"#));
+        assert!(!has_comments(r#"{ "escaped": "\" //" }"#));
+        assert!(!has_comments("{ \"a\": [1, 2,], }"));
+    }
+}
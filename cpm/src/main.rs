@@ -1,9 +1,11 @@
 use clap::{ CommandFactory, Parser };
 use spdlog::prelude::*;
+use std::path::{ Path, PathBuf };
 
 use crate::errors::errors::RuntimeErrors;
 use crate::internal::settings::Settings;
 use crate::internal::logger::Loggers;
+use crate::internal::project_root;
 
 mod commands;
 mod internal;
@@ -20,11 +22,11 @@ struct Cli {
     #[clap(long, global = true, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
     pub no_init: bool,
 
-    // TEST FLAG. DO NOT USE IN PRODUCTION.
-    /// Force settings reinitialization.
-    /// WARNING: This will overwrite the current settings file. This will break the current state if already initialized in the current directory.
-    #[clap(long, short, global = true, action = clap::ArgAction::SetTrue, verbatim_doc_comment)]
-    pub force_reinit: bool,
+    /// Run as if CPM was started in DIR instead of the current directory (like 'git -C' or 'make -C').
+    /// The project root is searched upward from there. Project commands still run against the
+    /// project initialized by this CPM installation.
+    #[clap(short = 'C', global = true, value_name = "DIR", verbatim_doc_comment)]
+    pub directory: Option<PathBuf>,
 
     // TODO: Find a better logging solution. SPDLOG is not working as expected.
     // // Flag to show debug logs
@@ -39,26 +41,116 @@ fn main() {
 
     let cli = Cli::parse();
 
-    let mut settings = Settings::init(false).unwrap();
-
-    if cli.force_reinit {
-        settings = Settings::init(true).unwrap();
+    if let Some(dir) = &cli.directory {
+        if let Err(e) = std::env::set_current_dir(dir) {
+            RuntimeErrors::DirectoryNotAccessible(dir.display().to_string(), e.to_string()).exit();
+        }
     }
 
+    let settings = Settings::init(false).unwrap();
+
     check_supported_os(&settings);
     debug!("Settings:\n{:#?}", settings);
 
+    check_project_root(&settings, &cli.command);
+
     match cli.command {
         Some(commands::Commands::Init(add_args)) => commands::init::run(add_args, cli.no_init),
         Some(commands::Commands::Setup(add_args)) => commands::setup::run(add_args),
+        Some(commands::Commands::New(add_args)) => commands::new::run(add_args),
+        Some(commands::Commands::Templates(add_args)) => commands::templates::run(add_args),
         Some(commands::Commands::Build(add_args)) => {
-            if settings.initialized == false {
+            if !settings.initialized {
                 RuntimeErrors::NotInitialized.exit();
             } else {
                 commands::build::run(add_args);
             }
         }
         Some(commands::Commands::Cache(add_args)) => commands::cache::run(add_args),
+        Some(commands::Commands::Info(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::info::run(add_args);
+            }
+        }
+        Some(commands::Commands::Run(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::run::run(add_args);
+            }
+        }
+        Some(commands::Commands::Test(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::test::run(add_args);
+            }
+        }
+        Some(commands::Commands::Package(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::package::run(add_args);
+            }
+        }
+        Some(commands::Commands::Clean(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::clean::run(add_args);
+            }
+        }
+        Some(commands::Commands::CmakeCache(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::cmake_cache::run(add_args);
+            }
+        }
+        Some(commands::Commands::Stats(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::stats::run(add_args);
+            }
+        }
+        Some(commands::Commands::Coverage(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::coverage::run(add_args);
+            }
+        }
+        Some(commands::Commands::Add(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::add::run(add_args);
+            }
+        }
+        Some(commands::Commands::Remove(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::remove::run(add_args);
+            }
+        }
+        Some(commands::Commands::Deinit(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::deinit::run(add_args);
+            }
+        }
+        Some(commands::Commands::Ide(add_args)) => {
+            if !settings.initialized {
+                RuntimeErrors::NotInitialized.exit();
+            } else {
+                commands::ide::run(add_args);
+            }
+        }
         None => {
             if let Err(e) = Cli::command().print_help() {
                 eprintln!("Failed to print help information: {}", e);
@@ -79,3 +171,40 @@ fn check_supported_os(settings: &Settings) {
         _ => RuntimeErrors::NotSupportedOS(Some(env.to_string())).exit(),
     }
 }
+
+// Project commands always run against the initialized project (settings.working_dir), the settings
+// belong to the CPM installation. Started from inside another project they would silently use the
+// initialized one instead, so they are refused. Otherwise they run from the project root, as if
+// CPM was started there.
+fn check_project_root(settings: &Settings, command: &Option<commands::Commands>) {
+    use commands::Commands;
+
+    let uses_project = !matches!(
+        command,
+        None |
+            Some(Commands::Init(_)) |
+            Some(Commands::Setup(_)) |
+            Some(Commands::Cache(_)) |
+            Some(Commands::New(_)) |
+            Some(Commands::Templates(_))
+    );
+    if !settings.initialized || !uses_project {
+        return;
+    }
+
+    let Ok(current_dir) = std::env::current_dir() else {
+        return;
+    };
+    if let Some(root) = project_root::discover(&current_dir) {
+        if !project_root::is_within(&root, Path::new(&settings.working_dir)) {
+            RuntimeErrors::ProjectRootMismatch(
+                root.display().to_string(),
+                settings.working_dir.clone()
+            ).exit();
+        }
+    }
+
+    if let Err(e) = std::env::set_current_dir(&settings.working_dir) {
+        RuntimeErrors::DirectoryNotAccessible(settings.working_dir.clone(), e.to_string()).exit();
+    }
+}